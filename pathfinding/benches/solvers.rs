@@ -0,0 +1,34 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use pathfinding::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn solve<'a, A: CreateAlgo<'a> + SearchAlgo>(maze: &'a Maze, start: usize, finish: usize) -> Option<Vec<usize>> {
+    A::new(maze, start, finish).solve()
+}
+
+// corner to corner on growing grids, up to a million cells
+fn large_grids(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_grids");
+    group.sample_size(10);
+    for size in [50, 200, 1000] {
+        let maze = create_labyrinth(size, size, &generators::RandomWalls, &mut StdRng::seed_from_u64(size as u64));
+        let finish = maze.len() - 1;
+        group.bench_with_input(BenchmarkId::new("bfs", size), &maze, |b, maze| {
+            b.iter(|| solve::<bfs::BFS>(maze, 0, finish))
+        });
+        group.bench_with_input(BenchmarkId::new("dfs", size), &maze, |b, maze| {
+            b.iter(|| solve::<dfs::DFS>(maze, 0, finish))
+        });
+        group.bench_with_input(BenchmarkId::new("dijkstra", size), &maze, |b, maze| {
+            b.iter(|| solve::<dijkstra::Dijkstra>(maze, 0, finish))
+        });
+        group.bench_with_input(BenchmarkId::new("astar", size), &maze, |b, maze| {
+            b.iter(|| solve::<astar::AStar>(maze, 0, finish))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, large_grids);
+criterion_main!(benches);
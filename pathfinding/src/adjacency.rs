@@ -0,0 +1,73 @@
+use crate::heuristic::{Euclidean, Heuristic};
+use crate::*;
+
+/// A graph spelled out edge by edge, for maps that are not grids: road networks, nav-mesh polygons...
+/// `AStar` measures the positions in straight lines, at the straight step cost of `movement`,
+/// so its paths stay optimal as long as no edge costs less than the distance between its ends.
+#[derive(Clone, Debug)]
+pub struct AdjacencyList {
+    pub edges: Vec<Vec<(NodeId, u32)>>,
+    /// The same edges seen from their end, kept in step by `add_edge`.
+    pub incoming: Vec<Vec<(NodeId, u32)>>,
+    pub positions: Vec<Option<(i32, i32)>>,
+    pub movement: Movement,
+}
+
+impl Default for AdjacencyList {
+    fn default() -> AdjacencyList {
+        AdjacencyList::new()
+    }
+}
+
+impl AdjacencyList {
+    pub fn new() -> AdjacencyList {
+        AdjacencyList { edges: Vec::new(), incoming: Vec::new(), positions: Vec::new(), movement: Movement::Four }
+    }
+
+    pub fn add_node(&mut self, position: Option<(i32, i32)>) -> NodeId {
+        self.edges.push(Vec::new());
+        self.incoming.push(Vec::new());
+        self.positions.push(position);
+        self.edges.len() - 1
+    }
+
+    /// One-way edge from `from` to `to`.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, cost: u32) {
+        assert!(cost >= 1, "edges cost at least 1");
+        self.edges[from].push((to, cost));
+        self.incoming[to].push((from, cost));
+    }
+
+    /// Edge usable both ways at the same cost.
+    pub fn connect(&mut self, a: NodeId, b: NodeId, cost: u32) {
+        self.add_edge(a, b, cost);
+        self.add_edge(b, a, cost);
+    }
+}
+
+impl Graph for AdjacencyList {
+    fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn edges(&self, node: NodeId) -> Vec<(NodeId, u32)> {
+        self.edges[node].clone()
+    }
+
+    fn position(&self, node: NodeId) -> Option<(i32, i32)> {
+        self.positions[node]
+    }
+
+    fn incoming(&self, node: NodeId) -> Vec<(NodeId, u32)> {
+        self.incoming[node].clone()
+    }
+
+    fn movement(&self) -> Movement {
+        self.movement
+    }
+
+    //edges are no grid steps, only the straight line is sure not to overestimate
+    fn heuristic(&self) -> &'static dyn Heuristic {
+        &Euclidean
+    }
+}
@@ -0,0 +1,152 @@
+use crate::search::BestFirst;
+use crate::*;
+
+/// Searches from both ends at once, one side per step.
+/// Every node labelled by both sides is a way through, the search stops once the two frontiers together
+/// are at least as far as the best of them: any path left undiscovered would have to cross both frontiers.
+struct Bidirectional<'a> {
+    start: usize,
+    finish: usize,
+    graph: &'a dyn Graph,
+    //edges cost their weight rather than one step
+    weighted: bool,
+    //each side is a Dijkstra, counting every edge as one step for BFS
+    forward: BestFirst,
+    backward: BestFirst,
+    best: u32,
+    meeting: usize,
+    backward_turn: bool,
+    solution: Vec<usize>,
+}
+
+impl<'a> Bidirectional<'a> {
+    fn new(graph: &'a dyn Graph, start: usize, finish: usize, weighted: bool) -> Bidirectional<'a> {
+        Bidirectional {
+            start,
+            finish,
+            graph,
+            weighted,
+            forward: BestFirst::new(graph.len(), start, finish, 0),
+            backward: BestFirst::new(graph.len(), finish, start, 0),
+            best: if start == finish { 0 } else { u32::MAX },
+            meeting: start,
+            backward_turn: false,
+            solution: Vec::new(),
+        }
+    }
+
+    /// Joins both halves at the meeting node, returning each link of the path from the start on as (node, antecedant, cost).
+    fn build_solution(&mut self) -> Vec<(usize, usize, u32)> {
+        let mut links = Vec::new();
+        let mut index = self.meeting;
+        while index != self.start {
+            let antecedant = self.forward.antecedants[index];
+            links.push((index, antecedant, self.forward.distances[index]));
+            index = antecedant;
+        }
+        links.reverse();
+        let mut index = self.meeting;
+        while index != self.finish {
+            let next = self.backward.antecedants[index];
+            links.push((next, index, self.best - self.backward.distances[next]));
+            index = next;
+        }
+
+        self.solution = links.iter().rev().map(|(index, _, _)| *index).collect();
+        self.solution.push(self.start);
+        links
+    }
+
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        while self.iter_solve(&mut |_| {}).is_none() {}
+
+        if self.best != u32::MAX {
+            Some(self.solution.clone())
+        } else {
+            //unreachable
+            None
+        }
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        let is_done = match (self.forward.peek(), self.backward.peek()) {
+            (Some(forward), Some(backward)) => forward.saturating_add(backward) >= self.best,
+            _ => true,
+        };
+        if is_done {
+            if self.best == u32::MAX {
+                observer(SearchEvent::Exhausted);
+                return Some(false);
+            }
+            //point the whole path towards the start, so it can be walked back from the finish like any other search
+            observer(SearchEvent::FrontierChanged { backward: false });
+            for (node, from, cost) in self.build_solution() {
+                observer(SearchEvent::CostUpdated { node, from, cost });
+            }
+            observer(SearchEvent::GoalReached);
+            return Some(true);
+        }
+
+        let backward = self.backward_turn;
+        self.backward_turn = !backward;
+        observer(SearchEvent::FrontierChanged { backward });
+        let (side, other) = if backward { (&mut self.backward, &self.forward) } else { (&mut self.forward, &self.backward) };
+
+        let node_index = side.pop(observer).unwrap();
+        side.close(node_index);
+        observer(SearchEvent::NodeExpanded(node_index));
+
+        let edges = if backward { self.graph.incoming(node_index) } else { self.graph.edges(node_index) };
+        for (neighbour_index, cost) in edges {
+            let distance = side.distances[node_index] + if self.weighted { cost } else { 1 };
+            if !side.relax(neighbour_index, node_index, distance, 0, observer) {
+                continue;
+            }
+
+            //the other side got here too, that is a way through
+            if other.reached(neighbour_index) && distance + other.distances[neighbour_index] < self.best {
+                self.best = distance + other.distances[neighbour_index];
+                self.meeting = neighbour_index;
+            }
+        }
+        None
+    }
+}
+
+/// BFS from both ends, fewest edges first.
+pub struct BidirectionalBFS<'a>(Bidirectional<'a>);
+
+impl<'a> CreateAlgo<'a> for BidirectionalBFS<'a> {
+    fn new(graph: &'a dyn Graph, start: usize, finish: usize) -> BidirectionalBFS<'a> {
+        BidirectionalBFS(Bidirectional::new(graph, start, finish, false))
+    }
+}
+
+impl SearchAlgo for BidirectionalBFS<'_> {
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        self.0.solve()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.0.iter_solve(observer)
+    }
+}
+
+/// Dijkstra from both ends, cheapest path first.
+pub struct BidirectionalDijkstra<'a>(Bidirectional<'a>);
+
+impl<'a> CreateAlgo<'a> for BidirectionalDijkstra<'a> {
+    fn new(graph: &'a dyn Graph, start: usize, finish: usize) -> BidirectionalDijkstra<'a> {
+        BidirectionalDijkstra(Bidirectional::new(graph, start, finish, true))
+    }
+}
+
+impl SearchAlgo for BidirectionalDijkstra<'_> {
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        self.0.solve()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.0.iter_solve(observer)
+    }
+}
@@ -0,0 +1,24 @@
+/// Fixed-size set of node indices, one bit per node.
+#[derive(Clone, Debug)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> BitSet {
+        BitSet { words: vec![0; len.div_ceil(64)] }
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Adds `index` to the set, returning `false` if it was already present.
+    pub fn insert(&mut self, index: usize) -> bool {
+        let word = &mut self.words[index / 64];
+        let mask = 1 << (index % 64);
+        let inserted = *word & mask == 0;
+        *word |= mask;
+        inserted
+    }
+}
@@ -0,0 +1,142 @@
+use crate::heuristic::{Heuristic, ScaledHeuristic, Zero};
+use crate::*;
+
+/// A node of the current path, with the edges out of it still to try.
+struct Frame {
+    index: usize,
+    distance: u32,
+    edges: Vec<(usize, u32)>,
+    next: usize,
+}
+
+/// Depth-first passes under a growing bound on distance + heuristic, each pass raising the bound to the
+/// smallest estimate that went past it. Only the current path is kept, so memory grows with its length,
+/// but every pass walks all the paths under the bound again: it gets slow on open maps with many loops.
+struct Deepening<'a> {
+    start: usize,
+    finish: usize,
+    graph: &'a dyn Graph,
+    heuristic: ScaledHeuristic<'a>,
+    //edges cost their weight rather than one step
+    weighted: bool,
+    bound: u32,
+    next_bound: u32,
+    path: Vec<Frame>,
+    found: bool,
+}
+
+impl<'a> Deepening<'a> {
+    fn new(graph: &'a dyn Graph, start: usize, finish: usize, heuristic: &'a dyn Heuristic, weight: f64, weighted: bool) -> Deepening<'a> {
+        let heuristic = ScaledHeuristic::new(graph, heuristic, finish, weight);
+        let next_bound = heuristic.estimate(start);
+        Deepening { start, finish, graph, heuristic, weighted, bound: 0, next_bound, path: Vec::new(), found: false }
+    }
+
+    fn push(&mut self, index: usize, distance: u32, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.path.push(Frame { index, distance, edges: self.graph.edges(index), next: 0 });
+        observer(SearchEvent::NodeExpanded(index));
+        if index == self.finish {
+            self.found = true;
+            observer(SearchEvent::GoalReached);
+            return Some(true);
+        }
+        None
+    }
+
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        while self.iter_solve(&mut |_| {}).is_none() {}
+
+        if self.found {
+            Some(self.path.iter().rev().map(|frame| frame.index).collect())
+        } else {
+            //unreachable
+            None
+        }
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        if self.path.is_empty() {
+            //the last pass cut nothing off, there is nowhere left to look
+            if self.next_bound == u32::MAX {
+                observer(SearchEvent::Exhausted);
+                return Some(false);
+            }
+            self.bound = self.next_bound;
+            self.next_bound = u32::MAX;
+            return self.push(self.start, 0, observer);
+        }
+
+        let frame = self.path.last_mut().unwrap();
+        let (index, distance) = (frame.index, frame.distance);
+        let (neighbour_index, cost) = match frame.edges.get(frame.next) {
+            Some(edge) => *edge,
+            None => {
+                //every way out tried, back up
+                self.path.pop();
+                if let Some(parent) = self.path.last() {
+                    observer(SearchEvent::NodeExpanded(parent.index));
+                }
+                return None;
+            }
+        };
+        frame.next += 1;
+
+        if self.path.iter().any(|frame| frame.index == neighbour_index) {
+            return None;
+        }
+        let distance = distance + if self.weighted { cost } else { 1 };
+        let estimate = distance.saturating_add(self.heuristic.estimate(neighbour_index));
+        if estimate > self.bound {
+            self.next_bound = self.next_bound.min(estimate);
+            return None;
+        }
+        observer(SearchEvent::NodeDiscovered { from: index, to: neighbour_index });
+        self.push(neighbour_index, distance, observer)
+    }
+}
+
+/// Iterative deepening DFS: depth-first passes limited to 1, 2, 3... edges, so the first path found has the fewest edges.
+#[allow(clippy::upper_case_acronyms)]
+pub struct IDDFS<'a>(Deepening<'a>);
+
+impl<'a> CreateAlgo<'a> for IDDFS<'a> {
+    fn new(graph: &'a dyn Graph, start: usize, finish: usize) -> IDDFS<'a> {
+        IDDFS(Deepening::new(graph, start, finish, &Zero, 1.0, false))
+    }
+}
+
+impl SearchAlgo for IDDFS<'_> {
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        self.0.solve()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.0.iter_solve(observer)
+    }
+}
+
+/// IDA*: iterative deepening on distance + heuristic, finding the same costs as `AStar` in memory proportional to the path.
+pub struct IDAStar<'a>(Deepening<'a>);
+
+impl<'a> IDAStar<'a> {
+    /// IDA* guided by `heuristic`, multiplied by `weight`. A weight above 1 trades optimality for fewer passes.
+    pub fn with_heuristic(graph: &'a dyn Graph, start: usize, finish: usize, heuristic: &'a dyn Heuristic, weight: f64) -> IDAStar<'a> {
+        IDAStar(Deepening::new(graph, start, finish, heuristic, weight, true))
+    }
+}
+
+impl<'a> CreateAlgo<'a> for IDAStar<'a> {
+    fn new(graph: &'a dyn Graph, start: usize, finish: usize) -> IDAStar<'a> {
+        IDAStar::with_heuristic(graph, start, finish, graph.heuristic(), 1.0)
+    }
+}
+
+impl SearchAlgo for IDAStar<'_> {
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        self.0.solve()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.0.iter_solve(observer)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::bitset::BitSet;
+use crate::generators::{edit, random_step, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+
+/// Aldous-Broder: random walk over the whole grid, opening the wall each time it enters a cell for the first time.
+/// Samples uniformly among all spanning trees, but takes a long time to cover big grids.
+pub struct AldousBroder;
+
+impl MazeGenerator for AldousBroder {
+    fn name(&self) -> &'static str {
+        "Aldous-Broder"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        let mut visited = BitSet::new(maze.len());
+        let mut index = rng.gen_range(0..maze.len());
+        visited.insert(index);
+        let mut remaining = maze.len() - 1;
+
+        while remaining > 0 {
+            let d = random_step(maze, index, rng);
+            let next = maze.neighbour(index, d).unwrap();
+            if visited.insert(next) {
+                edit(maze, MazeEdit::Carve { index, d }, observer);
+                remaining -= 1;
+            }
+            index = next;
+        }
+    }
+}
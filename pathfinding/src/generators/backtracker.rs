@@ -0,0 +1,40 @@
+use crate::bitset::BitSet;
+use crate::generators::{edit, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+
+/// Randomised depth-first search: walks to a random unvisited neighbour, backs up at dead ends.
+/// Gives a perfect maze with long winding corridors. Uses an explicit stack, so large grids are fine.
+pub struct RecursiveBacktracker;
+
+impl MazeGenerator for RecursiveBacktracker {
+    fn name(&self) -> &'static str {
+        "Recursive backtracker"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        let mut visited = BitSet::new(maze.len());
+        let first = rng.gen_range(0..maze.len());
+        visited.insert(first);
+        let mut stack = vec![first];
+
+        while let Some(&index) = stack.last() {
+            let unvisited: Vec<Direction> = Direction::ORTHOGONALS
+                .iter()
+                .copied()
+                .filter(|d| maze.neighbour(index, *d).is_some_and(|n| !visited.contains(n)))
+                .collect();
+
+            if unvisited.is_empty() {
+                stack.pop();
+                continue;
+            }
+
+            let d = unvisited[rng.gen_range(0..unvisited.len())];
+            let next = maze.neighbour(index, d).unwrap();
+            edit(maze, MazeEdit::Carve { index, d }, observer);
+            visited.insert(next);
+            stack.push(next);
+        }
+    }
+}
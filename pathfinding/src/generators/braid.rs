@@ -0,0 +1,53 @@
+use crate::generators::{edit, MazeEdit};
+use crate::*;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+
+fn is_dead_end(maze: &Maze, index: usize) -> bool {
+    maze[index].neighbours.iter().filter(|d| !d.is_diagonal()).count() == 1
+}
+
+/// Removes `fraction` of the dead ends of `maze`, rounded to whole dead ends, by knocking out one of their walls,
+/// preferring a wall that also frees a neighbouring dead end. 0 keeps the maze as is, 1 leaves no dead end.
+/// Run it before `Maze::set_movement`, the diagonals are derived from the walls.
+pub fn braid(maze: &mut Maze, fraction: f64, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+    //leave the random stream untouched, so seeds give the same mazes as without braiding
+    if fraction <= 0.0 {
+        return;
+    }
+    let mut dead_ends: Vec<usize> = (0..maze.len()).filter(|i| is_dead_end(maze, *i)).collect();
+    dead_ends.shuffle(rng);
+    let kept = dead_ends.len() - (dead_ends.len() as f64 * fraction.clamp(0.0, 1.0)).round() as usize;
+    //a carve may free two dead ends at once, so they are counted as they go rather than per wall knocked out
+    let mut remaining = dead_ends.len();
+
+    for &index in dead_ends.iter() {
+        if remaining <= kept {
+            break;
+        }
+        //an earlier removal may already have opened this one
+        if !is_dead_end(maze, index) {
+            continue;
+        }
+        let walled: Vec<Direction> = Direction::ORTHOGONALS
+            .iter()
+            .copied()
+            .filter(|d| !maze[index].neighbours.contains(d) && maze.neighbour(index, *d).is_some_and(|n| !maze[n].blocked))
+            .collect();
+        let (joining, others): (Vec<Direction>, Vec<Direction>) = walled.iter().copied().partition(|d| is_dead_end(maze, maze.neighbour(index, *d).unwrap()));
+        //opening into a walled-in cell would only move the dead end there
+        let (connected, walled_in): (Vec<Direction>, Vec<Direction>) = others.iter().copied().partition(|d| !maze[maze.neighbour(index, *d).unwrap()].neighbours.is_empty());
+        //freeing two dead ends with the last carve would overshoot, another dead end is tried instead
+        let preferred = if remaining > kept + 1 { vec![joining, connected, walled_in] } else { vec![connected, walled_in] };
+        let choices = match preferred.into_iter().find(|choices| !choices.is_empty()) {
+            Some(choices) => choices,
+            None => continue,
+        };
+        let d = choices[rng.gen_range(0..choices.len())];
+        let neighbour = maze.neighbour(index, d).unwrap();
+        let before = [index, neighbour].iter().filter(|i| is_dead_end(maze, **i)).count();
+        edit(maze, MazeEdit::Carve { index, d }, observer);
+        let after = [index, neighbour].iter().filter(|i| is_dead_end(maze, **i)).count();
+        remaining = remaining + after - before;
+    }
+}
@@ -0,0 +1,86 @@
+use crate::generators::{edit, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+
+/// Cellular automaton caves: fills cells at random, smooths them into rock and open areas,
+/// then blocks every open pocket but the largest so the whole cave stays reachable.
+/// Open cells are linked to all their open neighbours, giving an open map rather than corridors.
+pub struct Cave {
+    /// Chance of a cell starting as rock.
+    pub fill: f32,
+    /// Number of smoothing passes.
+    pub smoothing: u32,
+}
+
+/// Rock cells among the 8 around `(x, y)`, the outside of the map counts as rock.
+fn rock_around(maze: &Maze, rock: &[bool], x: i32, y: i32) -> usize {
+    Direction::ORTHOGONALS
+        .iter()
+        .chain(Direction::DIAGONALS.iter())
+        .filter(|d| {
+            let (dx, dy) = d.offset();
+            let (i, j) = (x + dx, y + dy);
+            i < 0 || i >= maze.width || j < 0 || j >= maze.height || rock[maze.get_array_val(i, j)]
+        })
+        .count()
+}
+
+impl MazeGenerator for Cave {
+    fn name(&self) -> &'static str {
+        "Cave"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        let mut rock: Vec<bool> = (0..maze.len()).map(|_| rng.gen::<f32>() < self.fill).collect();
+
+        //a cell turns to rock when most of its surroundings are rock, and opens up when few are
+        for _ in 0..self.smoothing {
+            rock = maze
+                .nodes
+                .iter()
+                .map(|node| match rock_around(maze, &rock, node.x, node.y) {
+                    count if count >= 5 => true,
+                    count if count <= 3 => false,
+                    _ => rock[maze.get_array_val(node.x, node.y)],
+                })
+                .collect();
+        }
+
+        //flood fill every open pocket, keeping the largest
+        let mut pocket = vec![usize::MAX; maze.len()];
+        let (mut largest, mut largest_size) = (usize::MAX, 0);
+        for first in 0..maze.len() {
+            if rock[first] || pocket[first] != usize::MAX {
+                continue;
+            }
+            pocket[first] = first;
+            let mut stack = vec![first];
+            let mut size = 0;
+            while let Some(index) = stack.pop() {
+                size += 1;
+                for d in Direction::ORTHOGONALS.iter() {
+                    if let Some(next) = maze.neighbour(index, *d).filter(|n| !rock[*n] && pocket[*n] == usize::MAX) {
+                        pocket[next] = first;
+                        stack.push(next);
+                    }
+                }
+            }
+            if size > largest_size {
+                largest = first;
+                largest_size = size;
+            }
+        }
+
+        for index in 0..maze.len() {
+            if pocket[index] != largest {
+                edit(maze, MazeEdit::Block { index }, observer);
+                continue;
+            }
+            for d in [Direction::Right, Direction::Down] {
+                if maze.neighbour(index, d).is_some_and(|n| pocket[n] == largest) {
+                    edit(maze, MazeEdit::Carve { index, d }, observer);
+                }
+            }
+        }
+    }
+}
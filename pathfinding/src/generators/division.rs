@@ -0,0 +1,48 @@
+use crate::generators::{edit, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+
+/// Recursive division: starts from an open field and splits it in two with a wall holding a single passage,
+/// then splits each half again until the chambers are one cell wide. Gives a perfect maze of long straight walls.
+pub struct RecursiveDivision;
+
+impl MazeGenerator for RecursiveDivision {
+    fn name(&self) -> &'static str {
+        "Recursive division"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        edit(maze, MazeEdit::OpenAll, observer);
+
+        //chambers still to divide, as [x, y, width, height]
+        let mut chambers = vec![[0, 0, maze.width, maze.height]];
+        while let Some([x, y, width, height]) = chambers.pop() {
+            if width < 2 || height < 2 {
+                continue;
+            }
+
+            let horizontal = if width == height { rng.gen::<bool>() } else { height > width };
+            if horizontal {
+                //wall below row `wall`, passage at column `passage`
+                let wall = rng.gen_range(y..y + height - 1);
+                let passage = rng.gen_range(x..x + width);
+                for column in (x..x + width).filter(|column| *column != passage) {
+                    let index = maze.get_array_val(column, wall);
+                    edit(maze, MazeEdit::BuildWall { index, d: Direction::Down }, observer);
+                }
+                chambers.push([x, y, width, wall - y + 1]);
+                chambers.push([x, wall + 1, width, y + height - wall - 1]);
+            } else {
+                //wall right of column `wall`, passage at row `passage`
+                let wall = rng.gen_range(x..x + width - 1);
+                let passage = rng.gen_range(y..y + height);
+                for row in (y..y + height).filter(|row| *row != passage) {
+                    let index = maze.get_array_val(wall, row);
+                    edit(maze, MazeEdit::BuildWall { index, d: Direction::Right }, observer);
+                }
+                chambers.push([x, y, wall - x + 1, height]);
+                chambers.push([wall + 1, y, x + width - wall - 1, height]);
+            }
+        }
+    }
+}
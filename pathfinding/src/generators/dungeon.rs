@@ -0,0 +1,145 @@
+use crate::generators::{edit, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+
+/// Rooms and corridors: the map is split in two over and over (binary space partitioning),
+/// each leaf gets a room and the two halves of every split are joined by an L-shaped corridor.
+/// Everything else is rock, so the rooms are all reachable from each other and nothing more.
+pub struct Dungeon {
+    /// Smallest room side, leaves are only split while both halves can still hold such a room.
+    pub min_room: i32,
+    /// Wall rooms off from the corridors running along them, so they are only entered where a corridor leads in.
+    pub doors: bool,
+}
+
+#[derive(Clone, Copy)]
+struct Rect {
+    x: i32,
+    y: i32,
+    w: i32,
+    h: i32,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Cell {
+    Rock,
+    Room(usize),
+    Corridor,
+}
+
+impl Dungeon {
+    /// Splits `leaf` until it is too small, placing rooms in the leaves and returning the rooms of this part of the tree.
+    fn split(&self, leaf: Rect, rng: &mut dyn RngCore, rooms: &mut Vec<Rect>, corridors: &mut Vec<(Rect, Rect)>) -> std::ops::Range<usize> {
+        let min_leaf = self.min_room + 2;
+        let (can_w, can_h) = (leaf.w >= 2 * min_leaf, leaf.h >= 2 * min_leaf);
+        let vertical = match (can_w, can_h) {
+            (false, false) => {
+                rooms.push(room_in(leaf, self.min_room, rng));
+                return rooms.len() - 1..rooms.len();
+            }
+            (true, false) => true,
+            (false, true) => false,
+            //cut across the longer side, keeps the leaves close to square
+            (true, true) if leaf.w * 4 >= leaf.h * 5 => true,
+            (true, true) if leaf.h * 4 >= leaf.w * 5 => false,
+            (true, true) => rng.gen(),
+        };
+
+        let (first, second) = if vertical {
+            let cut = rng.gen_range(min_leaf..=leaf.w - min_leaf);
+            (Rect { w: cut, ..leaf }, Rect { x: leaf.x + cut, w: leaf.w - cut, ..leaf })
+        } else {
+            let cut = rng.gen_range(min_leaf..=leaf.h - min_leaf);
+            (Rect { h: cut, ..leaf }, Rect { y: leaf.y + cut, h: leaf.h - cut, ..leaf })
+        };
+        let first = self.split(first, rng, rooms, corridors);
+        let second = self.split(second, rng, rooms, corridors);
+        let a = rooms[rng.gen_range(first.clone())];
+        let b = rooms[rng.gen_range(second.clone())];
+        corridors.push((a, b));
+        first.start..second.end
+    }
+}
+
+/// A room of random size inside `leaf`, kept off the leaf border when there is space for it.
+fn room_in(leaf: Rect, min_room: i32, rng: &mut dyn RngCore) -> Rect {
+    let side = |len: i32, rng: &mut dyn RngCore| {
+        let max = (len - 2).max(1);
+        rng.gen_range(min_room.min(max)..=max)
+    };
+    let offset = |len: i32, side: i32, rng: &mut dyn RngCore| {
+        let margin = if len - side >= 2 { 1 } else { 0 };
+        rng.gen_range(margin..=len - side - margin)
+    };
+    let (w, h) = (side(leaf.w, rng), side(leaf.h, rng));
+    Rect { x: leaf.x + offset(leaf.w, w, rng), y: leaf.y + offset(leaf.h, h, rng), w, h }
+}
+
+impl MazeGenerator for Dungeon {
+    fn name(&self) -> &'static str {
+        "Dungeon"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        let mut rooms = Vec::new();
+        let mut corridors = Vec::new();
+        self.split(Rect { x: 0, y: 0, w: maze.width, h: maze.height }, rng, &mut rooms, &mut corridors);
+
+        let mut cells = vec![Cell::Rock; maze.len()];
+        for (room, r) in rooms.iter().enumerate() {
+            for y in r.y..r.y + r.h {
+                for x in r.x..r.x + r.w {
+                    cells[maze.get_array_val(x, y)] = Cell::Room(room);
+                }
+            }
+        }
+
+        //every step of a corridor stays open, whatever it runs through
+        let mut links: Vec<(usize, Direction)> = Vec::new();
+        for (a, b) in corridors {
+            let (mut x, mut y) = (rng.gen_range(a.x..a.x + a.w), rng.gen_range(a.y..a.y + a.h));
+            let (to_x, to_y) = (rng.gen_range(b.x..b.x + b.w), rng.gen_range(b.y..b.y + b.h));
+            let horizontal_first: bool = rng.gen();
+            while (x, y) != (to_x, to_y) {
+                let d = if (horizontal_first && x != to_x) || y == to_y {
+                    if x < to_x { Direction::Right } else { Direction::Left }
+                } else if y < to_y {
+                    Direction::Down
+                } else {
+                    Direction::Up
+                };
+                let index = maze.get_array_val(x, y);
+                links.push((index, d));
+                let (dx, dy) = d.offset();
+                x += dx;
+                y += dy;
+                let next = maze.get_array_val(x, y);
+                if cells[next] == Cell::Rock {
+                    cells[next] = Cell::Corridor;
+                }
+            }
+        }
+
+        for index in 0..maze.len() {
+            if cells[index] == Cell::Rock {
+                edit(maze, MazeEdit::Block { index }, observer);
+                continue;
+            }
+            for d in [Direction::Right, Direction::Down] {
+                let joined = match maze.neighbour(index, d).map(|n| (cells[index], cells[n])) {
+                    Some((_, Cell::Rock)) | None => false,
+                    Some((Cell::Room(a), Cell::Room(b))) => a == b || !self.doors,
+                    Some(_) => !self.doors,
+                };
+                if joined {
+                    edit(maze, MazeEdit::Carve { index, d }, observer);
+                }
+            }
+        }
+        for (index, d) in links {
+            if !maze[index].neighbours.contains(&d) {
+                edit(maze, MazeEdit::Carve { index, d }, observer);
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::generators::{edit, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+
+/// Eller's algorithm, one row at a time: only the current row's sets are kept, so memory stays
+/// proportional to the width however many rows are produced.
+/// Each row comes out as nodes with `y` set to the row number and walls to the row above already matched.
+pub struct EllerRows<R: RngCore> {
+    width: i32,
+    height: Option<i32>,
+    rng: R,
+    row: i32,
+    //set of each cell of the next row, `None` where nothing comes down from above
+    sets: Vec<Option<usize>>,
+    next_set: usize,
+}
+
+impl<R: RngCore> EllerRows<R> {
+    /// Rows of a `width` wide maze, endless when `height` is `None`.
+    pub fn new(width: i32, height: Option<i32>, rng: R) -> EllerRows<R> {
+        assert!(width > 0, "maze must be at least 1 cell wide");
+        EllerRows { width, height, rng, row: 0, sets: vec![None; width as usize], next_set: 0 }
+    }
+}
+
+impl<R: RngCore> Iterator for EllerRows<R> {
+    type Item = Vec<Node>;
+
+    fn next(&mut self) -> Option<Vec<Node>> {
+        if self.height.is_some_and(|height| self.row >= height) {
+            return None;
+        }
+        let is_last = self.height == Some(self.row + 1);
+        let width = self.width as usize;
+
+        let mut nodes: Vec<Node> = (0..self.width).map(|x| Node { x, y: self.row, neighbours: Vec::new(), cost: 1, blocked: false }).collect();
+        let mut sets: Vec<usize> = Vec::with_capacity(width);
+        for (node, from_above) in nodes.iter_mut().zip(self.sets.iter()) {
+            match *from_above {
+                Some(set) => {
+                    node.neighbours.push(Direction::Up);
+                    sets.push(set);
+                }
+                None => {
+                    sets.push(self.next_set);
+                    self.next_set += 1;
+                }
+            }
+        }
+
+        //join neighbouring cells of different sets, all of them on the last row so everything ends up connected
+        for x in 0..width - 1 {
+            if sets[x] != sets[x + 1] && (is_last || self.rng.gen::<bool>()) {
+                nodes[x].neighbours.push(Direction::Right);
+                nodes[x + 1].neighbours.push(Direction::Left);
+                let (kept, merged) = (sets[x], sets[x + 1]);
+                sets.iter_mut().filter(|set| **set == merged).for_each(|set| *set = kept);
+            }
+        }
+
+        //every set goes down at least once, through its last cell if the coin flips never did
+        self.sets = vec![None; width];
+        if !is_last {
+            let mut went_down = false;
+            for x in 0..width {
+                let set_ends = x + 1 == width || sets[x + 1] != sets[x];
+                if self.rng.gen::<bool>() || (set_ends && !went_down) {
+                    nodes[x].neighbours.push(Direction::Down);
+                    self.sets[x] = Some(sets[x]);
+                    went_down = true;
+                }
+                if set_ends {
+                    went_down = false;
+                }
+            }
+        }
+
+        self.row += 1;
+        Some(nodes)
+    }
+}
+
+pub struct Eller;
+
+impl MazeGenerator for Eller {
+    fn name(&self) -> &'static str {
+        "Eller"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        for row in EllerRows::new(maze.width, Some(maze.height), rng) {
+            for node in row {
+                let index = maze.get_array_val(node.x, node.y);
+                for d in node.neighbours.into_iter().filter(|d| *d == Direction::Right || *d == Direction::Down) {
+                    edit(maze, MazeEdit::Carve { index, d }, observer);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::bitset::BitSet;
+use crate::generators::{edit, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+use std::collections::VecDeque;
+
+/// Which of the active cells the growing tree extends next.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Selection {
+    /// The last cell added, same mazes as the recursive backtracker.
+    Newest,
+    /// Any active cell, close to Prim.
+    Random,
+    /// The first cell added, long straight corridors.
+    Oldest,
+    /// The newest cell with probability `newest`, otherwise a random one.
+    Mixed { newest: f64 },
+}
+
+/// Growing tree: keeps a list of active cells, carves from one of them to an unvisited neighbour
+/// and drops it once it has none left. The `selection` policy decides the texture of the maze.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GrowingTree {
+    pub selection: Selection,
+}
+
+/// The policies offered by the viewer.
+pub const GROWING_TREES: [GrowingTree; 5] = [
+    GrowingTree { selection: Selection::Newest },
+    GrowingTree { selection: Selection::Random },
+    GrowingTree { selection: Selection::Oldest },
+    GrowingTree { selection: Selection::Mixed { newest: 0.75 } },
+    GrowingTree { selection: Selection::Mixed { newest: 0.5 } },
+];
+
+impl GrowingTree {
+    fn select(&self, active: usize, rng: &mut dyn RngCore) -> usize {
+        match self.selection {
+            Selection::Newest => active - 1,
+            Selection::Random => rng.gen_range(0..active),
+            Selection::Oldest => 0,
+            Selection::Mixed { newest } if rng.gen::<f64>() < newest => active - 1,
+            Selection::Mixed { .. } => rng.gen_range(0..active),
+        }
+    }
+}
+
+impl MazeGenerator for GrowingTree {
+    fn name(&self) -> &'static str {
+        "Growing tree"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        let mut visited = BitSet::new(maze.len());
+        let first = rng.gen_range(0..maze.len());
+        visited.insert(first);
+        let mut active = VecDeque::from(vec![first]);
+
+        while !active.is_empty() {
+            let chosen = self.select(active.len(), rng);
+            let index = active[chosen];
+            let unvisited: Vec<Direction> = Direction::ORTHOGONALS
+                .iter()
+                .copied()
+                .filter(|d| maze.neighbour(index, *d).is_some_and(|n| !visited.contains(n)))
+                .collect();
+
+            if unvisited.is_empty() {
+                //keeps the order, the newest and oldest cells stay at the ends
+                active.remove(chosen);
+                continue;
+            }
+
+            let d = unvisited[rng.gen_range(0..unvisited.len())];
+            let next = maze.neighbour(index, d).unwrap();
+            edit(maze, MazeEdit::Carve { index, d }, observer);
+            visited.insert(next);
+            active.push_back(next);
+        }
+    }
+}
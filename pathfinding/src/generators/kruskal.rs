@@ -0,0 +1,64 @@
+use crate::generators::{edit, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+/// Union-find over cell indices, with path halving and union by size.
+struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(len: usize) -> DisjointSet {
+        DisjointSet { parents: (0..len).collect(), sizes: vec![1; len] }
+    }
+
+    fn find(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    /// Merges the sets of `a` and `b`, returning `false` if they already were the same set.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        true
+    }
+}
+
+/// Randomised Kruskal: knocks down walls in random order whenever they separate two unconnected regions.
+/// Gives a perfect maze with many short dead ends.
+pub struct Kruskal;
+
+impl MazeGenerator for Kruskal {
+    fn name(&self) -> &'static str {
+        "Kruskal"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        let mut walls: Vec<(usize, Direction)> = (0..maze.len())
+            .flat_map(|index| [(index, Direction::Right), (index, Direction::Down)])
+            .filter(|(index, d)| maze.neighbour(*index, *d).is_some())
+            .collect();
+        walls.shuffle(rng);
+
+        let mut regions = DisjointSet::new(maze.len());
+        for (index, d) in walls {
+            let other = maze.neighbour(index, d).unwrap();
+            if regions.union(index, other) {
+                edit(maze, MazeEdit::Carve { index, d }, observer);
+            }
+        }
+    }
+}
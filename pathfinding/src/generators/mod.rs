@@ -0,0 +1,86 @@
+use crate::*;
+use rand::{Rng, RngCore};
+
+pub mod aldous_broder;
+pub mod backtracker;
+pub mod braid;
+pub mod cave;
+pub mod division;
+pub mod dungeon;
+pub mod eller;
+pub mod growing_tree;
+pub mod kruskal;
+pub mod prim;
+pub mod wilson;
+
+/// A uniformly random direction leading to a cell inside the grid, walls ignored.
+fn random_step(maze: &Maze, index: usize, rng: &mut dyn RngCore) -> Direction {
+    let inside: Vec<Direction> = Direction::ORTHOGONALS.iter().copied().filter(|d| maze.neighbour(index, *d).is_some()).collect();
+    inside[rng.gen_range(0..inside.len())]
+}
+
+/// A change made to the maze while it is generated, replaying them in order on a fully walled maze rebuilds it.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum MazeEdit {
+    Carve { index: usize, d: Direction },
+    BuildWall { index: usize, d: Direction },
+    Block { index: usize },
+    OpenAll,
+}
+
+impl MazeEdit {
+    pub fn apply(self, maze: &mut Maze) {
+        match self {
+            MazeEdit::Carve { index, d } => maze.carve(index, d),
+            MazeEdit::BuildWall { index, d } => maze.build_wall(index, d),
+            MazeEdit::Block { index } => maze.block(index),
+            MazeEdit::OpenAll => maze.open_all(),
+        }
+    }
+}
+
+/// Applies `edit` to `maze` and reports it.
+fn edit(maze: &mut Maze, edit: MazeEdit, observer: &mut dyn FnMut(MazeEdit)) {
+    edit.apply(maze);
+    observer(edit);
+}
+
+pub trait MazeGenerator {
+    fn name(&self) -> &'static str;
+    /// Shapes `maze`, which comes in with every wall up, reporting every change to `observer`.
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit));
+}
+
+/// Drops each right and bottom wall independently with probability `1 - PWALL`, regions may end up disconnected.
+pub struct RandomWalls;
+
+impl MazeGenerator for RandomWalls {
+    fn name(&self) -> &'static str {
+        "Random walls"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        for index in 0..maze.len() {
+            let (x, y) = (maze[index].x, maze[index].y);
+            if x < maze.width - 1 && rng.gen::<f32>() >= PWALL {
+                edit(maze, MazeEdit::Carve { index, d: Direction::Right }, observer);
+            }
+            if y < maze.height - 1 && rng.gen::<f32>() >= PWALL {
+                edit(maze, MazeEdit::Carve { index, d: Direction::Down }, observer);
+            }
+        }
+    }
+}
+
+pub const GENERATORS: [&dyn MazeGenerator; 10] = [
+    &RandomWalls,
+    &backtracker::RecursiveBacktracker,
+    &kruskal::Kruskal,
+    &prim::Prim,
+    &wilson::Wilson,
+    &aldous_broder::AldousBroder,
+    &division::RecursiveDivision,
+    &eller::Eller,
+    &cave::Cave { fill: 0.45, smoothing: 4 },
+    &dungeon::Dungeon { min_room: 3, doors: true },
+];
@@ -0,0 +1,35 @@
+use crate::bitset::BitSet;
+use crate::generators::{edit, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+
+/// Randomised Prim: grows the maze from one cell, each time opening a random wall between the maze and the rest.
+/// Gives a perfect maze with short corridors radiating from the first cell.
+pub struct Prim;
+
+impl MazeGenerator for Prim {
+    fn name(&self) -> &'static str {
+        "Prim"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        let mut in_maze = BitSet::new(maze.len());
+        let mut frontier: Vec<(usize, Direction)> = Vec::new();
+
+        let first = rng.gen_range(0..maze.len());
+        in_maze.insert(first);
+        frontier.extend(Direction::ORTHOGONALS.iter().map(|d| (first, *d)));
+
+        while !frontier.is_empty() {
+            let (index, d) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+            let next = match maze.neighbour(index, d) {
+                Some(next) if !in_maze.contains(next) => next,
+                _ => continue,
+            };
+
+            edit(maze, MazeEdit::Carve { index, d }, observer);
+            in_maze.insert(next);
+            frontier.extend(Direction::ORTHOGONALS.iter().map(|d| (next, *d)));
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::bitset::BitSet;
+use crate::generators::{edit, random_step, MazeEdit, MazeGenerator};
+use crate::*;
+use rand::{Rng, RngCore};
+
+/// Wilson: grows the maze with loop-erased random walks from each cell not yet in it.
+/// Samples uniformly among all spanning trees, like `AldousBroder`, and is much faster once the maze has grown.
+pub struct Wilson;
+
+impl MazeGenerator for Wilson {
+    fn name(&self) -> &'static str {
+        "Wilson"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore, observer: &mut dyn FnMut(MazeEdit)) {
+        let mut in_maze = BitSet::new(maze.len());
+        in_maze.insert(rng.gen_range(0..maze.len()));
+        //last direction the current walk left each cell by, overwriting it erases the loops
+        let mut exits = vec![Direction::Up; maze.len()];
+
+        for start in 0..maze.len() {
+            if in_maze.contains(start) {
+                continue;
+            }
+
+            let mut index = start;
+            while !in_maze.contains(index) {
+                let d = random_step(maze, index, rng);
+                exits[index] = d;
+                index = maze.neighbour(index, d).unwrap();
+            }
+
+            let mut index = start;
+            while !in_maze.contains(index) {
+                in_maze.insert(index);
+                edit(maze, MazeEdit::Carve { index, d: exits[index] }, observer);
+                index = maze.neighbour(index, exits[index]).unwrap();
+            }
+        }
+    }
+}
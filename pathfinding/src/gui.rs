@@ -0,0 +1,400 @@
+extern crate piston_window;
+
+use crate::bitset::BitSet;
+use crate::generators::MazeEdit;
+use crate::occupancy::OccupancyGrid;
+use crate::search::trace_path;
+use crate::terrain::Terrain;
+use crate::*;
+use piston_window::*;
+
+/// Cell and wall sizes in pixels, derived from the maze and the window dimensions.
+#[derive(Clone, Copy, Debug)]
+pub struct Layout {
+    pub colones: i32,
+    pub sizew: f64,
+    pub sizeh: f64,
+    pub wallwthick: f64,
+    pub wallhthick: f64,
+}
+
+impl Layout {
+    pub fn new<G: Grid + ?Sized>(grid: &G, view_size: [f64; 2]) -> Layout {
+        let sizew = view_size[0] / grid.width() as f64;
+        let sizeh = view_size[1] / grid.height() as f64;
+        Layout {
+            colones: grid.width(),
+            sizew,
+            sizeh,
+            wallwthick: sizew * 0.1,
+            wallhthick: sizeh * 0.1,
+        }
+    }
+
+    /// Layout filling the whole view of the given drawing context.
+    pub fn from_context<G: Grid + ?Sized>(grid: &G, context: &Context) -> Layout {
+        Layout::new(grid, context.get_view_size())
+    }
+
+    pub fn get_pos_node(&self, index: usize, x: &mut f64, y: &mut f64) {
+        let i = index as i32 % self.colones;
+        let j = index as i32 / self.colones;
+        *x = i as f64 * self.sizew;
+        *y = j as f64 * self.sizeh;
+    }
+}
+
+pub fn terrain_color(cost: u32) -> [f32; 4] {
+    match Terrain::from_cost(cost) {
+        Terrain::Road => [1.0, 0.0, 0.0, 1.0],
+        Terrain::Grass => [0.55, 0.75, 0.3, 1.0],
+        Terrain::Mud => [0.5, 0.35, 0.2, 1.0],
+        Terrain::Water => [0.25, 0.45, 0.9, 1.0],
+    }
+}
+
+pub fn draw_node(c: &Context, g: &mut G2d, layout: &Layout, index: usize, node: &Node) {
+    let Layout { sizew, sizeh, wallwthick, wallhthick, .. } = *layout;
+    let mut x: f64 = 0.0;
+    let mut y: f64 = 0.0;
+    layout.get_pos_node(index, &mut x, &mut y);
+    if node.blocked {
+        rectangle([0.2, 0.2, 0.2, 1.0], [x, y, sizew, sizeh], c.transform, g);
+        return;
+    }
+    let pos = [x + wallwthick, y + wallhthick, sizew -  2.0 * wallwthick, sizeh - 2.0 * wallhthick];
+    rectangle(terrain_color(node.cost), pos, c.transform, g);
+
+    if !node.neighbours.contains(&Direction::Up) {
+        rectangle([0.0, 0.0, 0.0, 1.0], [x, y, sizew, wallhthick], c.transform, g);
+    }
+
+    if !node.neighbours.contains(&Direction::Down) {
+        rectangle([0.0, 0.0, 0.0, 1.0], [x, y + sizeh - wallhthick, sizew, wallhthick], c.transform, g);
+    }
+
+    if !node.neighbours.contains(&Direction::Left) {
+        rectangle([0.0, 0.0, 0.0, 1.0], [x, y, wallwthick, sizeh], c.transform, g);
+    }
+
+    if !node.neighbours.contains(&Direction::Right) {
+        rectangle([0.0, 0.0, 0.0, 1.0], [x + sizew - wallwthick, y, wallwthick, sizeh], c.transform, g);
+    }
+}
+
+pub fn draw_labyrinth(c: &Context, g: &mut G2d, labyrinth: &Maze) {
+    let layout = Layout::from_context(labyrinth, c);
+    labyrinth.nodes.iter().enumerate().for_each(|(i, node)| draw_node(c, g, &layout, i, node));
+}
+
+/// Blocked cells are filled, open ones left to the background.
+pub fn draw_occupancy(c: &Context, g: &mut G2d, grid: &OccupancyGrid) {
+    let layout = Layout::from_context(grid, c);
+    grid.blocked.iter().enumerate().filter(|(_, blocked)| **blocked).for_each(|(i, _)| {
+        let (mut x, mut y) = (0.0, 0.0);
+        layout.get_pos_node(i, &mut x, &mut y);
+        rectangle([0.2, 0.2, 0.2, 1.0], [x, y, layout.sizew, layout.sizeh], c.transform, g);
+    });
+}
+
+/// Grids the viewer knows how to draw.
+pub trait DrawGrid: Grid {
+    fn draw(&self, c: &Context, g: &mut G2d);
+}
+
+impl DrawGrid for Maze {
+    fn draw(&self, c: &Context, g: &mut G2d) {
+        draw_labyrinth(c, g, self);
+    }
+}
+
+impl DrawGrid for OccupancyGrid {
+    fn draw(&self, c: &Context, g: &mut G2d) {
+        draw_occupancy(c, g, self);
+    }
+}
+
+pub fn draw_link(context: &Context, g: &mut G2d, layout: &Layout, n: usize, m: usize, color: [f32; 4]) {
+    if n == m { return; }
+
+    let Layout { sizew, sizeh, .. } = *layout;
+    let (mut a, mut b, mut c, mut d) = (0.0, 0.0, 0.0, 0.0);
+    if n % layout.colones as usize != m % layout.colones as usize && n / layout.colones as usize != m / layout.colones as usize {
+        //diagonal step, a rectangle would cover the two cells beside it
+        layout.get_pos_node(n, &mut a, &mut b);
+        layout.get_pos_node(m, &mut c, &mut d);
+        line_from_to(
+            color,
+            sizew.min(sizeh) * 0.05,
+            [a + sizew / 2.0, b + sizeh / 2.0],
+            [c + sizew / 2.0, d + sizeh / 2.0],
+            context.transform,
+            g,
+        );
+        return;
+    }
+    if n <= m {
+        layout.get_pos_node(n, &mut a, &mut b);
+        layout.get_pos_node(m, &mut c, &mut d);
+    } else {
+        layout.get_pos_node(m, &mut a, &mut b);
+        layout.get_pos_node(n, &mut c, &mut d);
+    }
+    a = a + sizew / 2.0 - sizew * 0.05;
+    b = b + sizeh / 2.0 - sizeh * 0.05;
+    c = c + sizew / 2.0 + sizew * 0.05;
+    d = d + sizeh / 2.0 + sizeh * 0.05;
+    rectangle(
+        color,
+        [a, b, c - a, d - b],
+        context.transform,
+        g,
+    );
+}
+
+/// Straight segments between the centres of consecutive cells of `path`.
+pub fn draw_polyline(context: &Context, g: &mut G2d, layout: &Layout, path: &[usize], color: [f32; 4]) {
+    let Layout { sizew, sizeh, .. } = *layout;
+    let (mut a, mut b, mut c, mut d) = (0.0, 0.0, 0.0, 0.0);
+    for w in path.windows(2) {
+        layout.get_pos_node(w[0], &mut a, &mut b);
+        layout.get_pos_node(w[1], &mut c, &mut d);
+        line_from_to(
+            color,
+            sizew.min(sizeh) * 0.05,
+            [a + sizew / 2.0, b + sizeh / 2.0],
+            [c + sizew / 2.0, d + sizeh / 2.0],
+            context.transform,
+            g,
+        );
+    }
+}
+
+/// Paths going cell by cell are drawn as links, paths skipping cells, as any-angle searches give, as a polyline.
+pub fn draw_path(context: &Context, g: &mut G2d, layout: &Layout, path: &[usize]) {
+    let colones = layout.colones as usize;
+    let stepwise = path.windows(2).all(|w| {
+        let (n, m) = (w[0].min(w[1]), w[0].max(w[1]));
+        m - n == colones || (m - n == 1 && n % colones != colones - 1)
+    });
+    if stepwise {
+        path.windows(2).for_each(|w| draw_link(context, g, layout, w[0], w[1], [0.0, 1.0, 0.0, 1.0]));
+    } else {
+        draw_polyline(context, g, layout, path, [0.0, 1.0, 0.0, 1.0]);
+    }
+}
+
+pub fn draw_node_indicator(context: &Context, g: &mut G2d, layout: &Layout, index: usize, color: [f32; 4]) {
+    let Layout { sizew, sizeh, wallwthick, wallhthick, .. } = *layout;
+    let (mut x, mut y) = (0.0, 0.0);
+    layout.get_pos_node(index, &mut x, &mut y);
+    rectangle(
+        color,
+        [
+            x + sizew / 2.0 - wallwthick,
+            y + sizeh / 2.0 - wallhthick,
+            wallwthick,
+            wallhthick,
+        ],
+        context.transform,
+        g,
+    );
+}
+
+/// Search state rebuilt from the `SearchEvent`s of any `SearchAlgo`, so every algorithm is drawn the same way.
+pub struct SearchView<'a> {
+    maze: &'a dyn DrawGrid,
+    start: usize,
+    finish: usize,
+    current: usize,
+    antecedants: Vec<usize>,
+    //bidirectional searches only, which nodes were reached from the finish
+    backward: bool,
+    current_backward: Option<usize>,
+    reached_backward: Vec<bool>,
+    //jump point search only
+    jump_points: BitSet,
+    result: Option<bool>,
+    solution: Vec<usize>,
+    on_solution: BitSet,
+}
+
+impl<'a> SearchView<'a> {
+    pub fn new(maze: &'a dyn DrawGrid, start: usize, finish: usize) -> SearchView<'a> {
+        SearchView {
+            maze,
+            start,
+            finish,
+            current: start,
+            antecedants: vec![usize::MAX; maze.len()],
+            backward: false,
+            current_backward: None,
+            reached_backward: vec![false; maze.len()],
+            jump_points: BitSet::new(maze.len()),
+            result: None,
+            solution: Vec::new(),
+            on_solution: BitSet::new(maze.len()),
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.result.is_some()
+    }
+
+    pub fn observe(&mut self, event: SearchEvent) {
+        match event {
+            SearchEvent::NodeExpanded(node) if self.backward => self.current_backward = Some(node),
+            SearchEvent::NodeExpanded(node) => self.current = node,
+            SearchEvent::NodeDiscovered { from, to: node } | SearchEvent::CostUpdated { node, from, .. } => {
+                self.antecedants[node] = from;
+                self.reached_backward[node] = self.backward;
+            }
+            SearchEvent::FrontierChanged { backward } => self.backward = backward,
+            SearchEvent::JumpPoint(node) => {
+                self.jump_points.insert(node);
+            }
+            SearchEvent::GoalReached => {
+                self.result = Some(true);
+                self.build_solution();
+            }
+            SearchEvent::Exhausted => self.result = Some(false),
+        }
+    }
+
+    fn build_solution(&mut self) {
+        self.solution = trace_path(&self.antecedants, self.start, self.finish);
+        self.on_solution = BitSet::new(self.antecedants.len());
+        for index in self.solution.iter() {
+            self.on_solution.insert(*index);
+        }
+    }
+
+    pub fn draw(&self, context: &Context, g: &mut G2d) {
+        let layout = Layout::from_context(self.maze, context);
+        clear([1.0, 1.0, 1.0, 1.0], g);
+        self.maze.draw(context, g);
+
+        match self.result {
+            Some(true) => draw_path(context, g, &layout, &self.solution),
+            Some(false) => {}
+            None => {
+                self.antecedants.iter().enumerate().for_each(|(index, antecedant)| {
+                    if *antecedant != usize::MAX {
+                        let color = if self.reached_backward[index] { [0.8, 0.0, 0.8, 1.0] } else { [0.0, 1.0, 0.0, 1.0] };
+                        draw_link(context, g, &layout, index, *antecedant, color)
+                    }
+                });
+                draw_node_indicator(context, g, &layout, self.current, [1.0, 0.5, 0.0, 1.0]);
+                if let Some(current) = self.current_backward {
+                    draw_node_indicator(context, g, &layout, current, [0.5, 0.0, 1.0, 1.0]);
+                }
+            }
+        }
+        //once solved, only the jump points the path turns at
+        (0..self.antecedants.len())
+            .filter(|index| self.jump_points.contains(*index) && (self.result != Some(true) || self.on_solution.contains(*index)))
+            .for_each(|index| draw_node_indicator(context, g, &layout, index, [1.0, 0.8, 0.0, 1.0]));
+
+        draw_node_indicator(context, g, &layout, self.start, [0.0, 0.0, 1.0, 1.0]);
+        draw_node_indicator(context, g, &layout, self.finish, [0.0, 0.0, 1.0, 1.0]);
+    }
+}
+
+/// Stepping controls shared by the solvers and the generators: left click runs `step` once,
+/// the left click after it reports an outcome leaves, right click leaves at any time.
+pub fn step_through<S>(
+    window: &mut PistonWindow,
+    state: &mut S,
+    draw: impl Fn(&S, &Context, &mut G2d),
+    mut step: impl FnMut(&mut S) -> Option<bool>,
+) {
+    let mut is_done = false;
+    while let Some(e) = window.next() {
+        window.draw_2d(&e, |c, g, _| {
+            draw(state, &c, g);
+        });
+
+        if let Some(button) = e.press_args() {
+            if button == Button::Mouse(MouseButton::Left) {
+                if is_done {
+                    break;
+                }
+                is_done = step(state).is_some();
+            } 
+            else if button == Button::Mouse(MouseButton::Right) {
+                break;
+            }
+        }
+    }
+}
+
+/// Steps `pathfinder` one `iter_solve` per left click.
+pub fn control_solve<G: DrawGrid>(window: &mut PistonWindow, maze: &G, start: usize, finish: usize, pathfinder: &mut dyn SearchAlgo) {
+    let mut view = SearchView::new(maze, start, finish);
+    step_through(window, &mut view, |view, c, g| view.draw(c, g), |view| {
+        let result = pathfinder.iter_solve(&mut |event| view.observe(event));
+        if let Some(r) = result {
+            let (mut a, mut b, mut c, mut d) = (0, 0, 0, 0);
+            maze.get_coord_vals(start, &mut a, &mut b);
+            maze.get_coord_vals(finish, &mut c, &mut d);
+            if r {
+                println!("Success from [{}, {}] to [{}, {}]", a, b, c, d);
+            } else {
+                println!("Failure from [{}, {}] to [{}, {}]", a, b, c, d);
+            }
+        }
+        result
+    });
+}
+
+/// Replays the construction of a maze one edit per left click, starting from a fully walled `maze`.
+pub fn control_generate(window: &mut PistonWindow, maze: &mut Maze, edits: &[MazeEdit]) {
+    step_through(window, &mut (maze, 0usize), |(maze, replayed), c, g| {
+        clear([1.0, 1.0, 1.0, 1.0], g);
+        draw_labyrinth(c, g, maze);
+        //highlight the last change
+        if let Some(MazeEdit::Carve { index, .. }) | Some(MazeEdit::BuildWall { index, .. }) = replayed.checked_sub(1).and_then(|i| edits.get(i)) {
+            draw_node_indicator(c, g, &Layout::from_context(&**maze, c), *index, [1.0, 0.5, 0.0, 1.0]);
+        }
+    }, |(maze, replayed)| {
+        if let Some(edit) = edits.get(*replayed) {
+            edit.apply(maze);
+            *replayed += 1;
+        }
+        if *replayed == edits.len() { Some(true) } else { None }
+    });
+}
+
+/// Shows `visible` rows of an endless maze at a time, scrolling down one row per wheel notch or down arrow.
+/// Rows are pulled from `rows` as they come into view and dropped once they leave it, right click leaves.
+pub fn scroll_rows(window: &mut PistonWindow, rows: &mut dyn Iterator<Item = Vec<Node>>, visible: usize) {
+    let shown: Vec<Vec<Node>> = rows.take(visible).collect();
+    //a maze needs at least one row
+    if shown.is_empty() {
+        return;
+    }
+    let width = shown[0].len();
+    let mut maze = Maze::new(width as i32, shown.len() as i32);
+    maze.nodes = shown.into_iter().flatten().collect();
+    let mut top = 0;
+    while let Some(e) = window.next() {
+        window.draw_2d(&e, |c, g, _| {
+            clear([1.0, 1.0, 1.0, 1.0], g);
+            draw_labyrinth(&c, g, &maze);
+        });
+
+        let scrolled = e.mouse_scroll_args().is_some_and(|[_, dy]| dy < 0.0) || e.press_args() == Some(Button::Keyboard(Key::Down));
+        if scrolled {
+            if let Some(row) = rows.next() {
+                //the top row leaves, the new one comes in at the bottom, in the same buffer
+                maze.nodes.drain(..width);
+                maze.nodes.extend(row);
+                top += 1;
+                window.set_title(format!("THE MAZE - rows {} to {}", top, top + maze.height as usize - 1));
+            }
+        }
+        if e.press_args() == Some(Button::Mouse(MouseButton::Right)) {
+            break;
+        }
+    }
+}
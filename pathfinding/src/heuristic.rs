@@ -0,0 +1,125 @@
+use crate::*;
+
+/// Estimate of the cost between two cells, in the step costs of the given movement on unit cells.
+/// `AStar` scales it by the cheapest cell of the maze so admissible heuristics stay admissible with terrain.
+pub trait Heuristic {
+    fn name(&self) -> &'static str;
+    fn estimate(&self, movement: &Movement, dx: i32, dy: i32) -> f64;
+}
+
+fn straight_cost(movement: &Movement) -> f64 {
+    movement.step_cost(Direction::Up) as f64
+}
+
+fn diagonal_cost(movement: &Movement) -> f64 {
+    match movement {
+        //two straight steps make a diagonal
+        Movement::Four => 2.0 * straight_cost(movement),
+        Movement::Eight(_) => movement.step_cost(Direction::UpRight) as f64,
+    }
+}
+
+pub struct Manhattan;
+
+impl Heuristic for Manhattan {
+    fn name(&self) -> &'static str {
+        "Manhattan"
+    }
+
+    fn estimate(&self, movement: &Movement, dx: i32, dy: i32) -> f64 {
+        straight_cost(movement) * (dx.abs() + dy.abs()) as f64
+    }
+}
+
+pub struct Euclidean;
+
+impl Heuristic for Euclidean {
+    fn name(&self) -> &'static str {
+        "Euclidean"
+    }
+
+    fn estimate(&self, movement: &Movement, dx: i32, dy: i32) -> f64 {
+        //a unit of straight line never costs less than this, even along cheap diagonals
+        let unit = straight_cost(movement).min(diagonal_cost(movement) / std::f64::consts::SQRT_2);
+        unit * (dx as f64).hypot(dy as f64)
+    }
+}
+
+pub struct Octile;
+
+impl Heuristic for Octile {
+    fn name(&self) -> &'static str {
+        "Octile"
+    }
+
+    fn estimate(&self, movement: &Movement, dx: i32, dy: i32) -> f64 {
+        let (dx, dy) = (dx.abs() as f64, dy.abs() as f64);
+        let straight = straight_cost(movement);
+        let diagonal = diagonal_cost(movement).min(2.0 * straight);
+        if diagonal < straight {
+            //zig-zagging diagonals may beat straight lines, only count the steps
+            diagonal * dx.max(dy)
+        } else {
+            straight * dx.max(dy) + (diagonal - straight) * dx.min(dy)
+        }
+    }
+}
+
+pub struct Chebyshev;
+
+impl Heuristic for Chebyshev {
+    fn name(&self) -> &'static str {
+        "Chebyshev"
+    }
+
+    fn estimate(&self, movement: &Movement, dx: i32, dy: i32) -> f64 {
+        straight_cost(movement).min(diagonal_cost(movement)) * dx.abs().max(dy.abs()) as f64
+    }
+}
+
+/// No estimate at all, `AStar` then expands nodes in the same order as `Dijkstra`.
+pub struct Zero;
+
+impl Heuristic for Zero {
+    fn name(&self) -> &'static str {
+        "Zero"
+    }
+
+    fn estimate(&self, _movement: &Movement, _dx: i32, _dy: i32) -> f64 {
+        0.0
+    }
+}
+
+pub const HEURISTICS: [&dyn Heuristic; 5] = [&Manhattan, &Euclidean, &Octile, &Chebyshev, &Zero];
+
+/// The tightest admissible built-in heuristic for the movement.
+pub fn default_heuristic(movement: &Movement) -> &'static dyn Heuristic {
+    match movement {
+        Movement::Four => &Manhattan,
+        Movement::Eight(_) => &Octile,
+    }
+}
+
+/// `heuristic` from any node of `graph` to a finish, in the costs of its edges.
+pub(crate) struct ScaledHeuristic<'a> {
+    graph: &'a dyn Graph,
+    heuristic: &'a dyn Heuristic,
+    goal: Option<(i32, i32)>,
+    scale: f64,
+}
+
+impl<'a> ScaledHeuristic<'a> {
+    /// Multiplied by `weight`, a weight above 1 makes it overestimate.
+    pub fn new(graph: &'a dyn Graph, heuristic: &'a dyn Heuristic, finish: usize, weight: f64) -> ScaledHeuristic<'a> {
+        //a step costs at least the cheapest cell, so a scaled admissible estimate stays admissible
+        ScaledHeuristic { graph, heuristic, goal: graph.position(finish), scale: graph.min_cost() as f64 * weight }
+    }
+
+    pub fn estimate(&self, index: usize) -> u32 {
+        //without positions there is nothing to measure
+        match (self.graph.position(index), self.goal) {
+            (Some((x, y)), Some((fx, fy))) => (self.heuristic.estimate(&self.graph.movement(), x - fx, y - fy) * self.scale) as u32,
+            _ => 0,
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use crate::heuristic::{Heuristic, Octile};
+use crate::occupancy::OccupancyGrid;
+use crate::search::BestFirst;
+use crate::*;
+
+const DIRECTIONS: [Direction; 8] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+    Direction::UpLeft,
+    Direction::UpRight,
+    Direction::DownLeft,
+    Direction::DownRight,
+];
+
+fn slot(d: Direction) -> usize {
+    DIRECTIONS.iter().position(|other| *other == d).unwrap()
+}
+
+/// Diagonal steps need both cells beside them open, as in the 8-connected grids without corner cutting.
+fn can_step(grid: &OccupancyGrid, x: i32, y: i32, dx: i32, dy: i32) -> bool {
+    grid.is_open(x + dx, y + dy) && (dx == 0 || dy == 0 || (grid.is_open(x + dx, y) && grid.is_open(x, y + dy)))
+}
+
+/// Whether arriving at `(x, y)` by a straight step `(dx, dy)` leaves a neighbour that only this path reaches optimally.
+fn has_forced_neighbour(grid: &OccupancyGrid, x: i32, y: i32, dx: i32, dy: i32) -> bool {
+    if dx != 0 {
+        (grid.is_open(x, y - 1) && !grid.is_open(x - dx, y - 1)) || (grid.is_open(x, y + 1) && !grid.is_open(x - dx, y + 1))
+    } else {
+        (grid.is_open(x - 1, y) && !grid.is_open(x - 1, y - dy)) || (grid.is_open(x + 1, y) && !grid.is_open(x + 1, y - dy))
+    }
+}
+
+/// Directions worth following out of a node entered going `travel`, the others are covered by a path through another node.
+fn pruned_directions(travel: Option<Direction>) -> Vec<Direction> {
+    let travel = match travel {
+        Some(travel) => travel,
+        None => return DIRECTIONS.to_vec(),
+    };
+    let (dx, dy) = travel.offset();
+    let offsets = if dx != 0 && dy != 0 {
+        vec![(dx, 0), (0, dy), (dx, dy)]
+    } else if dx != 0 {
+        vec![(dx, 0), (0, 1), (0, -1), (dx, 1), (dx, -1)]
+    } else {
+        vec![(0, dy), (1, 0), (-1, 0), (1, dy), (-1, dy)]
+    };
+    offsets.into_iter().map(|(dx, dy)| Direction::from_offset(dx, dy).unwrap()).collect()
+}
+
+/// How far a jump goes from every open cell in every direction, precomputed once per grid for `JPSPlus`.
+/// A positive distance lands on a jump point, a negative or zero one is the number of steps before a wall.
+pub struct JumpTable {
+    distances: Vec<[i32; 8]>,
+}
+
+impl JumpTable {
+    pub fn new(grid: &OccupancyGrid) -> JumpTable {
+        let mut table = JumpTable { distances: vec![[0; 8]; grid.len()] };
+        //straight jumps first, the diagonal ones stop where a straight jump would find something
+        for d in Direction::ORTHOGONALS.iter().chain(Direction::DIAGONALS.iter()) {
+            let (dx, dy) = d.offset();
+            //walk against the direction so the cell ahead is always done first
+            let ys: Vec<i32> = if dy > 0 { (0..grid.height).rev().collect() } else { (0..grid.height).collect() };
+            let xs: Vec<i32> = if dx > 0 { (0..grid.width).rev().collect() } else { (0..grid.width).collect() };
+            for &y in ys.iter() {
+                for &x in xs.iter() {
+                    if !grid.is_open(x, y) || !can_step(grid, x, y, dx, dy) {
+                        continue;
+                    }
+                    let ahead = grid.get_array_val(x + dx, y + dy);
+                    let stops = if d.is_diagonal() {
+                        let (horizontal, vertical) = (Direction::from_offset(dx, 0).unwrap(), Direction::from_offset(0, dy).unwrap());
+                        table.distances[ahead][slot(horizontal)] > 0 || table.distances[ahead][slot(vertical)] > 0
+                    } else {
+                        has_forced_neighbour(grid, x + dx, y + dy, dx, dy)
+                    };
+                    let further = table.distances[ahead][slot(*d)];
+                    table.distances[grid.get_array_val(x, y)][slot(*d)] = if stops {
+                        1
+                    } else if further > 0 {
+                        further + 1
+                    } else {
+                        further - 1
+                    };
+                }
+            }
+        }
+        table
+    }
+
+    pub fn distance(&self, index: usize, d: Direction) -> i32 {
+        self.distances[index][slot(d)]
+    }
+}
+
+//where the successors of a node come from
+enum Jumps<'a> {
+    Online,
+    Table(&'a JumpTable),
+}
+
+/// Octile distance between two cells, at the step costs of `diagonals`.
+fn octile(grid: &OccupancyGrid, diagonals: &Diagonals, from: usize, to: usize) -> u32 {
+    let (mut x, mut y, mut tx, mut ty) = (0, 0, 0, 0);
+    grid.get_coord_vals(from, &mut x, &mut y);
+    grid.get_coord_vals(to, &mut tx, &mut ty);
+    Octile.estimate(&Movement::Eight(*diagonals), x - tx, y - ty) as u32
+}
+
+/// A* over jump points: straight and diagonal runs through open space are skipped in one go,
+/// only the cells where the way around an obstacle can change are ever put in the open set.
+struct JumpSearch<'a> {
+    grid: &'a OccupancyGrid,
+    jumps: Jumps<'a>,
+    diagonals: Diagonals,
+    search: BestFirst,
+    travel: Vec<Option<Direction>>,
+}
+
+impl<'a> JumpSearch<'a> {
+    fn new(grid: &'a OccupancyGrid, start: usize, finish: usize, jumps: Jumps<'a>) -> JumpSearch<'a> {
+        //the pruning rules only hold without corner cutting and with a diagonal step no cheaper than a straight one
+        //and no dearer than two, the grid's own step costs are kept when they fit
+        let diagonals = match grid.movement {
+            Movement::Eight(diagonals) if diagonals.straight_cost <= diagonals.diagonal_cost && diagonals.diagonal_cost <= 2 * diagonals.straight_cost => {
+                Diagonals { corner_cutting: false, ..diagonals }
+            }
+            _ => Diagonals::default(),
+        };
+        let score = octile(grid, &diagonals, start, finish);
+        JumpSearch { grid, jumps, diagonals, search: BestFirst::new(grid.len(), start, finish, score), travel: vec![None; grid.len()] }
+    }
+
+    fn estimate(&self, index: usize) -> u32 {
+        octile(self.grid, &self.diagonals, index, self.search.finish)
+    }
+
+    /// Follows `(dx, dy)` from `(x, y)` up to the next jump point, `None` when a wall comes first.
+    fn jump(&self, mut x: i32, mut y: i32, dx: i32, dy: i32) -> Option<(i32, i32)> {
+        loop {
+            if !can_step(self.grid, x, y, dx, dy) {
+                return None;
+            }
+            x += dx;
+            y += dy;
+            if self.grid.get_array_val(x, y) == self.search.finish {
+                return Some((x, y));
+            }
+            let stops = if dx != 0 && dy != 0 {
+                self.jump(x, y, dx, 0).is_some() || self.jump(x, y, 0, dy).is_some()
+            } else {
+                has_forced_neighbour(self.grid, x, y, dx, dy)
+            };
+            if stops {
+                return Some((x, y));
+            }
+        }
+    }
+
+    /// The node reached from `index` going `d`, with the number of steps taken.
+    fn successor(&self, index: usize, d: Direction) -> Option<(usize, i32)> {
+        if self.grid.blocked[index] {
+            return None;
+        }
+        let (dx, dy) = d.offset();
+        let (mut x, mut y, mut fx, mut fy) = (0, 0, 0, 0);
+        self.grid.get_coord_vals(index, &mut x, &mut y);
+        self.grid.get_coord_vals(self.search.finish, &mut fx, &mut fy);
+
+        let table = match self.jumps {
+            Jumps::Online => {
+                return self.jump(x, y, dx, dy).map(|(jx, jy)| (self.grid.get_array_val(jx, jy), (jx - x).abs().max((jy - y).abs())));
+            }
+            Jumps::Table(table) => table,
+        };
+        let distance = table.distance(index, d);
+        //the table knows nothing of the finish, stop level with it when it lies ahead
+        let (to_x, to_y) = (fx - x, fy - y);
+        let ahead = to_x.signum() == dx && to_y.signum() == dy;
+        let steps = if dx != 0 && dy != 0 {
+            if ahead && (to_x.abs() <= distance.abs() || to_y.abs() <= distance.abs()) {
+                to_x.abs().min(to_y.abs())
+            } else {
+                distance
+            }
+        } else if ahead && (to_x.abs() + to_y.abs()) <= distance.abs() {
+            to_x.abs() + to_y.abs()
+        } else {
+            distance
+        };
+        if steps > 0 {
+            Some((self.grid.get_array_val(x + dx * steps, y + dy * steps), steps))
+        } else {
+            None
+        }
+    }
+
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        while self.iter_solve(&mut |_| {}).is_none() {}
+        self.search.path()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        let node_index = match self.search.pop(observer) {
+            Some(index) => index,
+            None => return Some(false),
+        };
+
+        if node_index == self.search.finish {
+            observer(SearchEvent::GoalReached);
+            return Some(true);
+        }
+        observer(SearchEvent::NodeExpanded(node_index));
+
+        for d in pruned_directions(self.travel[node_index]) {
+            let (neighbour_index, steps) = match self.successor(node_index, d) {
+                Some(successor) => successor,
+                None => continue,
+            };
+            let step_cost = if d.is_diagonal() { self.diagonals.diagonal_cost } else { self.diagonals.straight_cost };
+            let distance = self.search.distances[node_index] + steps as u32 * step_cost;
+            let discovered = !self.search.reached(neighbour_index);
+            if self.search.relax(neighbour_index, node_index, distance, self.estimate(neighbour_index), observer) {
+                self.travel[neighbour_index] = Some(d);
+                if discovered {
+                    observer(SearchEvent::JumpPoint(neighbour_index));
+                }
+            }
+        }
+
+        self.search.close(node_index);
+        None
+    }
+}
+
+/// Jump point search on an occupancy grid, always 8-connected without corner cutting whatever the movement of the grid:
+/// its diagonal costs when it has some with `straight_cost <= diagonal_cost <= 2 * straight_cost`, `Diagonals::default()` otherwise.
+/// Same path costs as `AStar` with that movement, the path is given as its jump points, consecutive ones lie on a straight or diagonal line.
+#[allow(clippy::upper_case_acronyms)]
+pub struct JPS<'a>(JumpSearch<'a>);
+
+impl<'a> JPS<'a> {
+    pub fn new(grid: &'a OccupancyGrid, start: usize, finish: usize) -> JPS<'a> {
+        JPS(JumpSearch::new(grid, start, finish, Jumps::Online))
+    }
+}
+
+impl SearchAlgo for JPS<'_> {
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        self.0.solve()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.0.iter_solve(observer)
+    }
+}
+
+/// JPS+: jump point search reading its jumps from a `JumpTable` of the grid instead of scanning for them, moving as `JPS` does.
+#[allow(clippy::upper_case_acronyms)]
+pub struct JPSPlus<'a>(JumpSearch<'a>);
+
+impl<'a> JPSPlus<'a> {
+    pub fn new(grid: &'a OccupancyGrid, table: &'a JumpTable, start: usize, finish: usize) -> JPSPlus<'a> {
+        JPSPlus(JumpSearch::new(grid, start, finish, Jumps::Table(table)))
+    }
+}
+
+impl SearchAlgo for JPSPlus<'_> {
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        self.0.solve()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.0.iter_solve(observer)
+    }
+}
@@ -0,0 +1,382 @@
+use generators::MazeGenerator;
+use heuristic::{default_heuristic, Heuristic};
+use rand::RngCore;
+
+pub mod bfs;
+pub mod dfs;
+pub mod dijkstra;
+pub mod astar;
+pub mod bidirectional;
+pub mod deepening;
+pub mod heuristic;
+pub mod jps;
+pub mod theta;
+pub mod bitset;
+mod search;
+pub mod generators;
+pub mod terrain;
+pub mod occupancy;
+pub mod adjacency;
+
+#[cfg(feature = "gui")]
+pub mod gui;
+
+pub const PWALL: f32 = 0.35;
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ORTHOGONALS: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+    pub const DIAGONALS: [Direction; 4] = [Direction::UpLeft, Direction::UpRight, Direction::DownLeft, Direction::DownRight];
+
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        let (dx, dy) = self.offset();
+        Direction::from_offset(-dx, -dy).unwrap()
+    }
+
+    pub fn from_offset(dx: i32, dy: i32) -> Option<Direction> {
+        Direction::ORTHOGONALS.iter().chain(Direction::DIAGONALS.iter()).copied().find(|d| d.offset() == (dx, dy))
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        Direction::DIAGONALS.contains(&self)
+    }
+
+    /// The vertical and horizontal steps making up a diagonal.
+    pub fn split(self) -> Option<(Direction, Direction)> {
+        match self {
+            Direction::UpLeft => Some((Direction::Up, Direction::Left)),
+            Direction::UpRight => Some((Direction::Up, Direction::Right)),
+            Direction::DownLeft => Some((Direction::Down, Direction::Left)),
+            Direction::DownRight => Some((Direction::Down, Direction::Right)),
+            _ => None,
+        }
+    }
+}
+
+/// Settings of the 8-connected mode. Costs are integers, the defaults approximate 1 and √2.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct Diagonals {
+    pub straight_cost: u32,
+    pub diagonal_cost: u32,
+    /// Allow a diagonal step when only one of its two L-shaped detours is open.
+    pub corner_cutting: bool,
+}
+
+impl Default for Diagonals {
+    fn default() -> Diagonals {
+        Diagonals { straight_cost: 10, diagonal_cost: 14, corner_cutting: false }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Movement {
+    Four,
+    Eight(Diagonals),
+}
+
+impl Movement {
+    pub fn step_cost(&self, d: Direction) -> u32 {
+        match self {
+            Movement::Four => 1,
+            Movement::Eight(diagonals) if d.is_diagonal() => diagonals.diagonal_cost,
+            Movement::Eight(diagonals) => diagonals.straight_cost,
+        }
+    }
+}
+
+/// Nodes of a `Graph` are numbered from 0 to `len() - 1`, so solvers keep their state in vectors.
+pub type NodeId = usize;
+
+/// What the solvers run on: nodes, weighted edges and, when there is one, a position for the heuristics.
+pub trait Graph {
+    fn len(&self) -> usize;
+    /// The nodes one edge away from `node`, with the cost of that edge, at least 1.
+    fn edges(&self, node: NodeId) -> Vec<(NodeId, u32)>;
+    /// Where `node` lies, `None` when the graph has no geometry and `AStar` gets no estimate.
+    fn position(&self, node: NodeId) -> Option<(i32, i32)>;
+
+    /// The nodes with an edge into `node`, with the cost of that edge. Used by the searches running backwards from the finish,
+    /// the default goes through every edge of the graph.
+    fn incoming(&self, node: NodeId) -> Vec<(NodeId, u32)> {
+        (0..self.len()).flat_map(|from| self.edges(from).into_iter().filter(|(to, _)| *to == node).map(move |(_, cost)| (from, cost))).collect()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Step costs the heuristics assume between neighbouring positions.
+    fn movement(&self) -> Movement {
+        Movement::Four
+    }
+
+    /// Cheapest cost per step, used to keep heuristics admissible on weighted graphs.
+    fn min_cost(&self) -> u32 {
+        1
+    }
+
+    /// Heuristic `AStar` and `IDAStar` fall back on, it must never overestimate the cost between two positions.
+    fn heuristic(&self) -> &'static dyn Heuristic {
+        default_heuristic(&self.movement())
+    }
+}
+
+/// The neighbour interface of square grids, wall mazes and occupancy grids alike.
+/// Cells are indexed row by row.
+pub trait Grid: Graph {
+    fn width(&self) -> i32;
+    fn height(&self) -> i32;
+    fn is_blocked(&self, index: usize) -> bool;
+    /// The cells one step away from `index`, with the direction taken to reach them.
+    fn neighbours(&self, index: usize) -> Vec<(usize, Direction)>;
+    /// Cost of stepping into `to` in direction `d`.
+    fn edge_cost(&self, to: usize, d: Direction) -> u32;
+
+    fn get_array_val(&self, x: i32, y: i32) -> usize {
+        (x + y * self.width()) as usize
+    }
+
+    fn get_coord_vals(&self, index: usize, i: &mut i32, j: &mut i32) {
+        *i = index as i32 % self.width();
+        *j = index as i32 / self.width();
+    }
+}
+
+pub trait CreateAlgo<'a> {
+    fn new(graph: &'a dyn Graph, start: NodeId, finish: NodeId) -> Self;
+}
+
+/// What a single `iter_solve` step did, in the order it happened.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum SearchEvent {
+    /// The node was taken out of the frontier and its neighbours examined.
+    NodeExpanded(usize),
+    /// `to` was reached for the first time, through `from`.
+    NodeDiscovered { from: usize, to: usize },
+    /// The best known cost to `node` is now `cost`, through `from`.
+    CostUpdated { node: usize, from: usize, cost: u32 },
+    /// Sent by the bidirectional searches, the events after it belong to the search from the finish when `backward`, from the start otherwise.
+    FrontierChanged { backward: bool },
+    /// Sent by jump point search, `node` is a jump point: the searches only ever stop and turn there.
+    JumpPoint(usize),
+    GoalReached,
+    Exhausted,
+}
+
+pub trait SearchAlgo {
+    fn solve(&mut self) -> Option<Vec<usize>>;
+    /// Runs one step of the search, reporting every change to `observer`.
+    /// Returns `Some(true)` once the finish is reached and `Some(false)` once it is known to be unreachable.
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool>;
+}
+
+#[derive(Clone, Debug)]
+pub struct Node {
+    pub x: i32,
+    pub y: i32,
+    pub neighbours: Vec<Direction>,
+    /// Cost of stepping into this cell, at least 1.
+    pub cost: u32,
+    /// Solid rock rather than floor, a blocked cell has no neighbours and is never entered.
+    pub blocked: bool,
+}
+
+/// A `width` x `height` grid of nodes, stored row by row.
+#[derive(Clone, Debug)]
+pub struct Maze {
+    pub width: i32,
+    pub height: i32,
+    pub nodes: Vec<Node>,
+    pub movement: Movement,
+}
+
+impl Maze {
+    /// Creates a maze where every node is surrounded by walls.
+    pub fn new(width: i32, height: i32) -> Maze {
+        assert!(width > 0 && height > 0, "maze must be at least 1x1");
+        let mut maze = Maze { width, height, nodes: Vec::with_capacity((width * height) as usize), movement: Movement::Four };
+        for index in 0..(width * height) as usize {
+            let (mut x, mut y) = (0, 0);
+            maze.get_coord_vals(index, &mut x, &mut y);
+            maze.nodes.push(Node { x, y, neighbours: Vec::new(), cost: 1, blocked: false });
+        }
+        maze
+    }
+
+    /// Switches between 4- and 8-connected movement, deriving the diagonal links from the walls.
+    pub fn set_movement(&mut self, movement: Movement) {
+        self.movement = movement;
+        for node in self.nodes.iter_mut() {
+            node.neighbours.retain(|d| !d.is_diagonal());
+        }
+
+        let diagonals = match movement {
+            Movement::Four => return,
+            Movement::Eight(diagonals) => diagonals,
+        };
+        for index in 0..self.len() {
+            let (x, y) = (self.nodes[index].x, self.nodes[index].y);
+            for d in Direction::DIAGONALS.iter() {
+                let (dx, dy) = d.offset();
+                if x + dx < 0 || x + dx >= self.width || y + dy < 0 || y + dy >= self.height {
+                    continue;
+                }
+                let (vertical, horizontal) = d.split().unwrap();
+                let through_vertical = self.nodes[index].neighbours.contains(&vertical)
+                    && self[self.get_array_val(x, y + dy)].neighbours.contains(&horizontal);
+                let through_horizontal = self.nodes[index].neighbours.contains(&horizontal)
+                    && self[self.get_array_val(x + dx, y)].neighbours.contains(&vertical);
+                let open = if diagonals.corner_cutting {
+                    through_vertical || through_horizontal
+                } else {
+                    through_vertical && through_horizontal
+                };
+                if open {
+                    self.nodes[index].neighbours.push(*d);
+                }
+            }
+        }
+    }
+
+    /// Index of the cell next to `index` in direction `d`, if it is inside the grid.
+    pub fn neighbour(&self, index: usize, d: Direction) -> Option<usize> {
+        let (dx, dy) = d.offset();
+        let (x, y) = (self.nodes[index].x + dx, self.nodes[index].y + dy);
+        if x < 0 || x >= self.width || y < 0 || y >= self.height {
+            None
+        } else {
+            Some(self.get_array_val(x, y))
+        }
+    }
+
+    /// Opens the wall between `index` and its neighbour in direction `d`, on both sides.
+    pub fn carve(&mut self, index: usize, d: Direction) {
+        let other = self.neighbour(index, d).expect("cannot carve through the border of the maze");
+        if !self.nodes[index].neighbours.contains(&d) {
+            self.nodes[index].neighbours.push(d);
+        }
+        if !self.nodes[other].neighbours.contains(&d.opposite()) {
+            self.nodes[other].neighbours.push(d.opposite());
+        }
+    }
+
+    /// Puts back the wall between `index` and its neighbour in direction `d`, on both sides.
+    pub fn build_wall(&mut self, index: usize, d: Direction) {
+        let other = self.neighbour(index, d).expect("the border of the maze is always walled");
+        self.nodes[index].neighbours.retain(|n| *n != d);
+        self.nodes[other].neighbours.retain(|n| *n != d.opposite());
+    }
+
+    /// Fills the cell at `index`, walling it off from all its neighbours.
+    pub fn block(&mut self, index: usize) {
+        for d in self.nodes[index].neighbours.clone() {
+            self.build_wall(index, d);
+        }
+        self.nodes[index].blocked = true;
+    }
+
+    /// Removes every wall inside the grid, blocked cells stay walled off.
+    pub fn open_all(&mut self) {
+        for index in 0..self.len() {
+            for d in [Direction::Right, Direction::Down] {
+                if self.neighbour(index, d).is_some_and(|other| !self.nodes[index].blocked && !self.nodes[other].blocked) {
+                    self.carve(index, d);
+                }
+            }
+        }
+    }
+
+    pub fn get_array_val_from_pos_and_dir(&self, x: i32, y: i32, d: Direction) -> usize {
+        let (dx, dy) = d.offset();
+        self.get_array_val(x + dx, y + dy)
+    }
+}
+
+impl Graph for Maze {
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edges(&self, node: NodeId) -> Vec<(NodeId, u32)> {
+        self.neighbours(node).into_iter().map(|(to, d)| (to, self.edge_cost(to, d))).collect()
+    }
+
+    fn position(&self, node: NodeId) -> Option<(i32, i32)> {
+        Some((self.nodes[node].x, self.nodes[node].y))
+    }
+
+    //links always go both ways
+    fn incoming(&self, node: NodeId) -> Vec<(NodeId, u32)> {
+        self.neighbours(node).into_iter().map(|(from, d)| (from, self.edge_cost(node, d.opposite()))).collect()
+    }
+
+    fn movement(&self) -> Movement {
+        self.movement
+    }
+
+    fn min_cost(&self) -> u32 {
+        self.nodes.iter().map(|node| node.cost).min().unwrap_or(1)
+    }
+}
+
+impl Grid for Maze {
+    fn width(&self) -> i32 {
+        self.width
+    }
+
+    fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn is_blocked(&self, index: usize) -> bool {
+        self.nodes[index].blocked
+    }
+
+    fn neighbours(&self, index: usize) -> Vec<(usize, Direction)> {
+        let node = &self.nodes[index];
+        node.neighbours.iter().map(|d| (self.get_array_val_from_pos_and_dir(node.x, node.y, *d), *d)).collect()
+    }
+
+    fn edge_cost(&self, to: usize, d: Direction) -> u32 {
+        self.nodes[to].cost * self.movement.step_cost(d)
+    }
+}
+
+impl std::ops::Index<usize> for Maze {
+    type Output = Node;
+
+    fn index(&self, index: usize) -> &Node {
+        &self.nodes[index]
+    }
+}
+
+/// Builds a `width` x `height` maze with `generator`, drawing from `rng` only, so a seeded generator always gives back the same maze.
+pub fn create_labyrinth(width: i32, height: i32, generator: &dyn MazeGenerator, rng: &mut dyn RngCore) -> Maze {
+    let mut labyrinth = Maze::new(width, height);
+    generator.generate(&mut labyrinth, rng, &mut |_| {});
+    labyrinth
+}
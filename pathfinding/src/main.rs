@@ -1,231 +1,80 @@
 extern crate piston_window;
 
+use pathfinding::generators::braid::braid;
+use pathfinding::generators::eller::EllerRows;
+use pathfinding::generators::growing_tree::GROWING_TREES;
+use pathfinding::generators::{MazeEdit, MazeGenerator, GENERATORS};
+use pathfinding::gui::*;
+use pathfinding::heuristic::{Heuristic, HEURISTICS};
+use pathfinding::jps::{JumpTable, JPSPlus, JPS};
+use pathfinding::occupancy::OccupancyGrid;
+use pathfinding::terrain::paint_terrain;
+use pathfinding::theta::{LazyThetaStar, ThetaStar};
+use pathfinding::*;
 use piston_window::*;
-use rand::Rng;
-
-pub mod bfs;
-pub mod dfs;
-pub mod dijkstra;
-pub mod astar;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 const WIDTH: i32 = 640;
 const HEIGTH: i32 = 480;
 const LINES: i32 = 5;
 const COLONES: i32 = 5;
-const SIZE: i32 = LINES * COLONES;
-const SIZEW: f64 = WIDTH as f64 / COLONES as f64;
-const SIZEH: f64 = HEIGTH as f64 / LINES as f64;
-const WALLWTHICK: f64 = SIZEW * 0.1;
-const WALLHTHICK: f64 = SIZEH * 0.1;
-const PWALL: f32 = 0.35;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Copy, Clone)]
 enum Algo {
     BFS,
     DFS,
     Dijkstra,
     AStar,
+    BidirectionalBFS,
+    BidirectionalDijkstra,
+    IDDFS,
+    IDAStar,
+    ThetaStar,
+    LazyThetaStar,
 }
 
-#[derive(PartialEq, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-trait CreateAlgo<'a> {
-    fn new(maze: &'a Vec<Node>, start: usize, finish: usize) -> Self;
-}
-
-trait SearchAlgo {
-    fn solve(&mut self) -> Option<Vec<usize>>;
-    fn control_solve(&mut self, window: &mut PistonWindow);
-    fn iter_solve(&mut self) -> Option<bool>;
-}
-
-#[derive(Clone)]
-struct Node {
-    x: i32,
-    y: i32,
-    neighbours: Vec<Direction>,
-}
-
-fn get_array_val(x: i32, y: i32) -> usize {
-    return (x + y * COLONES) as usize;
-}
-
-fn get_array_val_from_pos_and_dir(x: i32, y: i32, d: Direction) -> usize {
-    match d {
-        Direction::Up => return get_array_val(x, y - 1),
-        Direction::Down => return get_array_val(x, y + 1),
-        Direction::Left => return get_array_val(x - 1, y),
-        Direction::Right => return get_array_val(x + 1, y),
-    };
-}
-
-fn get_coord_vals(index: usize, i: &mut i32, j: &mut i32) {
-    *i = index as i32 % COLONES;
-    *j = index as i32 / COLONES;
-}
-
-fn get_pos_node(index: usize, x: &mut f64, y: &mut f64) {
-    let mut i: i32 = 0;
-    let mut j: i32 = 0;
-    get_coord_vals(index, &mut i, &mut j);
-    *x = i as f64 * SIZEW;
-    *y = j as f64 * SIZEH;
-}
-
-fn generate_node(index: usize, labyrinth: &mut Vec<Node>, rng: &mut rand::rngs::ThreadRng) {
-    let mut x: i32 = 0;
-    let mut y: i32 = 0;
-    get_coord_vals(index, &mut x, &mut y);
-    labyrinth[index].x = x;
-    labyrinth[index].y = y;
-    if x > 0 {
-        if labyrinth[get_array_val(x - 1, y)]
-            .neighbours
-            .contains(&Direction::Right)
-        {
-            labyrinth[index].neighbours.push(Direction::Left);
-        }
-    }
-    if x < COLONES - 1 {
-        if rng.gen::<f32>() >= PWALL {
-            labyrinth[index].neighbours.push(Direction::Right);
-        }
-    }
-    if y > 0 {
-        if labyrinth[get_array_val(x, y - 1)]
-            .neighbours
-            .contains(&Direction::Down)
-        {
-            labyrinth[index].neighbours.push(Direction::Up);
-        }
-    }
-    if y < LINES - 1 {
-        if rng.gen::<f32>() >= PWALL {
-            labyrinth[index].neighbours.push(Direction::Down);
-        }
-    }
-}
-
-fn create_labyrinth() -> Vec<Node> {
-    let mut labyrinth = vec![
-        Node {
-            x: 0,
-            y: 0,
-            neighbours: Vec::new()
-        };
-        SIZE as usize
-    ];
-    let mut rng = rand::thread_rng();
-
-    for i in 0..labyrinth.len() {
-        generate_node(i, &mut labyrinth, &mut rng);
-    }
-
-    return labyrinth;
-}
-
-fn draw_node(c: &Context, g: &mut G2d, index: usize, node: &Node) {
-    let mut x: f64 = 0.0;
-    let mut y: f64 = 0.0;
-    get_pos_node(index, &mut x, &mut y);
-    let pos = [x + WALLWTHICK, y + WALLHTHICK, SIZEW -  2.0 * WALLWTHICK, SIZEH - 2.0 * WALLHTHICK];
-    rectangle([1.0, 0.0, 0.0, 1.0], pos, c.transform, g);
-
-    if !node.neighbours.contains(&Direction::Up) {
-        rectangle([0.0, 0.0, 0.0, 1.0], [x, y, SIZEW, WALLHTHICK], c.transform, g);
-    }
-
-    if !node.neighbours.contains(&Direction::Down) {
-        rectangle([0.0, 0.0, 0.0, 1.0], [x, y + SIZEH - WALLHTHICK, SIZEW, WALLHTHICK], c.transform, g);
-    }
-
-    if !node.neighbours.contains(&Direction::Left) {
-        rectangle([0.0, 0.0, 0.0, 1.0], [x, y, WALLWTHICK, SIZEH], c.transform, g);
-    }
-
-    if !node.neighbours.contains(&Direction::Right) {
-        rectangle([0.0, 0.0, 0.0, 1.0], [x + SIZEW - WALLWTHICK, y, WALLWTHICK, SIZEH], c.transform, g);
-    }
-}
-
-fn draw_labyrinth(c: &Context, g: &mut G2d, labyrinth: &Vec<Node>) {
-    labyrinth.iter().enumerate().for_each(|(i, node)| draw_node(c, g, i, node));
+/// What a click solves with: one of the solvers running on any grid, or jump point search, which needs an occupancy grid.
+#[derive(Copy, Clone)]
+enum Solver {
+    Grid(Algo),
+    JumpPoints { precomputed: bool },
 }
 
-fn draw_link(context: &Context, g: &mut G2d, n: usize, m: usize) {
-    if n == m { return; }
-
-    let (mut a, mut b, mut c, mut d) = (0.0, 0.0, 0.0, 0.0);
-    if n <= m {
-        get_pos_node(n, &mut a, &mut b);
-        get_pos_node(m, &mut c, &mut d);
-    } else {
-        get_pos_node(m, &mut a, &mut b);
-        get_pos_node(n, &mut c, &mut d);
-    }
-    a = a + SIZEW / 2.0 - SIZEW * 0.05;
-    b = b + SIZEH / 2.0 - SIZEH * 0.05;
-    c = c + SIZEW / 2.0 + SIZEW * 0.05;
-    d = d + SIZEH / 2.0 + SIZEH * 0.05;
-    rectangle(
-        [0.0, 1.0, 0.0, 1.0],
-        [a, b, c - a, d - b],
-        context.transform,
-        g,
-    );
+/// Heuristic and weight handed to A*, `None` picks the default heuristic of the maze movement.
+#[derive(Copy, Clone)]
+struct Guidance {
+    heuristic: Option<usize>,
+    weight: f64,
 }
 
-fn draw_path(context: &Context, g: &mut G2d, path: &Vec<usize>) {
-    for i in 0..(path.len() - 1) {
-        draw_link(context, g, path[i], path[i+1]);
+impl Guidance {
+    fn heuristic(&self, maze: &dyn Graph) -> &'static dyn Heuristic {
+        self.heuristic.map_or(maze.heuristic(), |i| HEURISTICS[i])
     }
 }
 
-fn draw_node_indicator(context: &Context, g: &mut G2d, index: usize, color: [f32; 4]) {
-    let (mut x, mut y) = (0.0, 0.0);
-    get_pos_node(index, &mut x, &mut y);
-    rectangle(
-        color,
-        [
-            x + SIZEW / 2.0 - WALLWTHICK,
-            y + SIZEH / 2.0 - WALLHTHICK,
-            WALLWTHICK,
-            WALLHTHICK,
-        ],
-        context.transform,
-        g,
-    );
-}
-
-fn create_algo(algo: Algo, start: usize, end: usize, maze: &Vec<Node>) -> Box<dyn SearchAlgo + '_> {
+fn create_algo<G: Grid>(algo: Algo, guidance: Guidance, start: usize, end: usize, maze: &G) -> Box<dyn SearchAlgo + '_> {
     match algo {
-        Algo::BFS => {
-          return Box::<bfs::BFS>::new(CreateAlgo::new(maze, start, end));
-        },
-        Algo::DFS => {
-            return Box::<dfs::DFS>::new(CreateAlgo::new(maze, start, end));
-        },
-        Algo::Dijkstra => {
-            return Box::<dijkstra::Dijkstra>::new(CreateAlgo::new(maze, start, end));
-        },
-        Algo::AStar => {
-            return Box::<astar::AStar>::new(CreateAlgo::new(maze, start, end));
-        }
+        Algo::BFS => Box::<bfs::BFS>::new(CreateAlgo::new(maze, start, end)),
+        Algo::DFS => Box::<dfs::DFS>::new(CreateAlgo::new(maze, start, end)),
+        Algo::Dijkstra => Box::<dijkstra::Dijkstra>::new(CreateAlgo::new(maze, start, end)),
+        Algo::AStar => Box::new(astar::AStar::with_heuristic(maze, start, end, guidance.heuristic(maze), guidance.weight)),
+        Algo::BidirectionalBFS => Box::<bidirectional::BidirectionalBFS>::new(CreateAlgo::new(maze, start, end)),
+        Algo::BidirectionalDijkstra => Box::<bidirectional::BidirectionalDijkstra>::new(CreateAlgo::new(maze, start, end)),
+        Algo::IDDFS => Box::<deepening::IDDFS>::new(CreateAlgo::new(maze, start, end)),
+        Algo::IDAStar => Box::new(deepening::IDAStar::with_heuristic(maze, start, end, guidance.heuristic(maze), guidance.weight)),
+        Algo::ThetaStar => Box::new(ThetaStar::new(maze, start, end)),
+        Algo::LazyThetaStar => Box::new(LazyThetaStar::new(maze, start, end)),
     }
 }
 
-fn solve_with_algo(window: &mut PistonWindow, algo: Algo, start: usize, end: usize, maze: &Vec<Node>) {
-    let mut pathfinder = create_algo(algo, start, end, maze);
-    
+fn solve_with_algo<G: DrawGrid>(window: &mut PistonWindow, pathfinder: &mut dyn SearchAlgo, start: usize, end: usize, maze: &G) {
+
     let (mut a, mut b, mut c, mut d) = (0, 0, 0, 0);     
-    get_coord_vals(start, &mut a, &mut b);
-    get_coord_vals(end, &mut c, &mut d);
+    maze.get_coord_vals(start, &mut a, &mut b);
+    maze.get_coord_vals(end, &mut c, &mut d);
 
    
     let path = pathfinder.solve();
@@ -237,12 +86,13 @@ fn solve_with_algo(window: &mut PistonWindow, algo: Algo, start: usize, end: usi
     window.set_lazy(true);
     while let Some(e) = window.next() {
         window.draw_2d(&e, |context, g, _| {
+            let layout = Layout::from_context(maze, &context);
 
-            draw_node_indicator(&context, g, start, [0.0, 0.0, 1.0, 1.0]);
-            draw_node_indicator(&context, g, end, [0.0, 0.0, 1.0, 1.0]);
+            draw_node_indicator(&context, g, &layout, start, [0.0, 0.0, 1.0, 1.0]);
+            draw_node_indicator(&context, g, &layout, end, [0.0, 0.0, 1.0, 1.0]);
 
-            if path.is_some() {
-                draw_path(&context, g, &(path.as_ref().unwrap()));
+            if let Some(path) = path.as_ref() {
+                draw_path(&context, g, &layout, path);
             }
         });
 
@@ -255,57 +105,239 @@ fn solve_with_algo(window: &mut PistonWindow, algo: Algo, start: usize, end: usi
     }
 }
 
-fn control_solve_with_algo(window: &mut PistonWindow, algo: Algo, start: usize, end: usize, maze: &Vec<Node>) {
-    create_algo(algo, start, end, maze).control_solve(window);
+/// Restarts the random stream from `seed` and builds its maze, later start/finish picks come from the same stream.
+/// `braiding` is the fraction of dead ends knocked out afterwards.
+fn generate(window: &mut PistonWindow, seed: u64, generator: &dyn MazeGenerator, braiding: f64, movement: Movement) -> (Maze, StdRng) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut labyrinth = create_labyrinth(COLONES, LINES, generator, &mut rng);
+    braid(&mut labyrinth, braiding, &mut rng, &mut |_| {});
+    labyrinth.set_movement(movement);
+    window.set_title(format!("THE MAZE - seed {}", seed));
+    println!("Seed {}", seed);
+    (labyrinth, rng)
 }
 
-fn main() {
-    let mut labyrinth = create_labyrinth();
-    let mut rng = rand::thread_rng();
-    let mut algo = Algo::AStar;
+/// Same as `generate`, but shows the construction step by step first.
+fn animate_generation(window: &mut PistonWindow, seed: u64, generator: &dyn MazeGenerator, braiding: f64, movement: Movement) -> (Maze, StdRng) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut labyrinth = Maze::new(COLONES, LINES);
+    let mut edits: Vec<MazeEdit> = Vec::new();
+    generator.generate(&mut labyrinth, &mut rng, &mut |edit| edits.push(edit));
+    braid(&mut labyrinth, braiding, &mut rng, &mut |edit| edits.push(edit));
+
+    control_generate(window, &mut Maze::new(COLONES, LINES), &edits);
+    labyrinth.set_movement(movement);
+    (labyrinth, rng)
+}
+
+/// A random cell to start or finish on, never a blocked one.
+fn random_cell(maze: &dyn Grid, rng: &mut StdRng) -> usize {
+    let open: Vec<usize> = (0..maze.len()).filter(|i| !maze.is_blocked(*i)).collect();
+    if open.is_empty() { 0 } else { open[rng.gen_range(0..open.len())] }
+}
+
+/// Picks a start and a finish on `grid` and solves it with the solver `create` builds, one step per click when `stepping`.
+fn solve_on<'a, G: DrawGrid>(
+    window: &mut PistonWindow,
+    grid: &G,
+    stepping: bool,
+    rng: &mut StdRng,
+    create: impl FnOnce(usize, usize) -> Box<dyn SearchAlgo + 'a>,
+) {
+    let start = random_cell(grid, rng);
+    let end = random_cell(grid, rng);
+    let mut pathfinder = create(start, end);
+    if stepping {
+        control_solve(window, grid, start, end, pathfinder.as_mut());
+    } else {
+        solve_with_algo(window, pathfinder.as_mut(), start, end, grid);
+    }
+}
 
-    println!("SIZEW : {},   SIZEH : {},    WALLWTHICK : {},    WALLHTHICK : {}", SIZEW, SIZEH, WALLWTHICK, WALLHTHICK);
+fn digit_key(button: Button) -> Option<char> {
+    let digits = [Key::D0, Key::D1, Key::D2, Key::D3, Key::D4, Key::D5, Key::D6, Key::D7, Key::D8, Key::D9];
+    digits.iter().position(|key| button == Button::Keyboard(*key)).and_then(|i| std::char::from_digit(i as u32, 10))
+}
+
+fn main() {
+    //`pathfinding <seed>` regenerates a given maze
+    let mut seed: u64 = match std::env::args().nth(1) {
+        Some(arg) => arg.parse().unwrap_or_else(|e| panic!("Invalid seed {}: {}", arg, e)),
+        None => rand::thread_rng().gen(),
+    };
+    let mut typed_seed = String::new();
+    let mut solver = Solver::Grid(Algo::AStar);
+    let mut generator: &dyn MazeGenerator = GENERATORS[0];
+    let mut listed_generator = 0;
+    let mut growing_tree = 0;
+    let mut braiding = 0.0;
+    let mut movement = Movement::Four;
+    let mut guidance = Guidance { heuristic: None, weight: 1.0 };
+    let mut occupancy = false;
 
     let title = "THE MAZE";
     let mut window: PistonWindow = WindowSettings::new(title, [WIDTH as f64, HEIGTH as f64])
         .exit_on_esc(true)
         .build()
         .unwrap_or_else(|e| panic!("Failed to build PistonWindow: {}", e));
+    let (mut labyrinth, mut rng) = generate(&mut window, seed, generator, braiding, movement);
+
+    let layout = Layout::new(&labyrinth, [WIDTH as f64, HEIGTH as f64]);
+    println!("SIZEW : {},   SIZEH : {},    WALLWTHICK : {},    WALLHTHICK : {}", layout.sizew, layout.sizeh, layout.wallwthick, layout.wallhthick);
 
     window.set_lazy(true);
     while let Some(e) = window.next() {
         window.draw_2d(&e, |c, g, _| {
             clear([1.0, 1.0, 1.0, 1.0], g);
-            draw_labyrinth(&c, g, &labyrinth);
+            if occupancy {
+                OccupancyGrid::from_maze(&labyrinth).draw(&c, g);
+            } else {
+                draw_labyrinth(&c, g, &labyrinth);
+            }
         });
 
         if let Some(button) = e.press_args() {
-            if button == Button::Mouse(MouseButton::Right) {
-                let start = rng.gen_range(0..labyrinth.len());
-                let end = rng.gen_range(0..labyrinth.len());                
-                solve_with_algo(&mut window, algo, start, end, &labyrinth);
-            }
-            else if button == Button::Mouse(MouseButton::Left) {
-                let start = rng.gen_range(0..labyrinth.len());
-                let end = rng.gen_range(0..labyrinth.len());
-                control_solve_with_algo(&mut window, algo, start, end, &labyrinth);
+            if button == Button::Mouse(MouseButton::Right) || button == Button::Mouse(MouseButton::Left) {
+                //right click solves at once, left click steps through the search
+                let stepping = button == Button::Mouse(MouseButton::Left);
+                match solver {
+                    Solver::JumpPoints { precomputed } => {
+                        let grid = OccupancyGrid::from_maze(&labyrinth);
+                        let table = if precomputed { Some(JumpTable::new(&grid)) } else { None };
+                        solve_on(&mut window, &grid, stepping, &mut rng, |start, end| match table.as_ref() {
+                            Some(table) => Box::new(JPSPlus::new(&grid, table, start, end)),
+                            None => Box::new(JPS::new(&grid, start, end)),
+                        });
+                    }
+                    Solver::Grid(algo) if occupancy => {
+                        let grid = OccupancyGrid::from_maze(&labyrinth);
+                        solve_on(&mut window, &grid, stepping, &mut rng, |start, end| create_algo(algo, guidance, start, end, &grid));
+                    }
+                    Solver::Grid(algo) => {
+                        solve_on(&mut window, &labyrinth, stepping, &mut rng, |start, end| create_algo(algo, guidance, start, end, &labyrinth));
+                    }
+                }
             }
             else if button == Button::Mouse(MouseButton::Middle) {
-                labyrinth = create_labyrinth();
+                seed = rand::thread_rng().gen();
+                (labyrinth, rng) = generate(&mut window, seed, generator, braiding, movement);
+            }
+            else if let Some(digit) = digit_key(button) {
+                typed_seed.push(digit);
+                println!("Seed to load : {}", typed_seed);
+            }
+            else if button == Button::Keyboard(Key::Return) || button == Button::Keyboard(Key::R) {
+                //enter loads the typed seed, r (or enter with nothing typed) replays the current one
+                if button == Button::Keyboard(Key::Return) && !typed_seed.is_empty() {
+                    seed = typed_seed.parse().unwrap_or(seed);
+                }
+                typed_seed.clear();
+                (labyrinth, rng) = generate(&mut window, seed, generator, braiding, movement);
+            }
+            else if button == Button::Keyboard(Key::G) {
+                listed_generator = (listed_generator + 1) % GENERATORS.len();
+                generator = GENERATORS[listed_generator];
+                println!("Using {} generator", generator.name());
+                (labyrinth, rng) = generate(&mut window, seed, generator, braiding, movement);
+            }
+            else if button == Button::Keyboard(Key::B) {
+                braiding = match braiding {
+                    b if b < 0.25 => 0.25,
+                    b if b < 0.5 => 0.5,
+                    b if b < 1.0 => 1.0,
+                    _ => 0.0,
+                };
+                println!("Removing {}% of dead ends", braiding * 100.0);
+                (labyrinth, rng) = generate(&mut window, seed, generator, braiding, movement);
+            }
+            else if button == Button::Keyboard(Key::A) {
+                (labyrinth, rng) = animate_generation(&mut window, seed, generator, braiding, movement);
+            }
+            else if button == Button::Keyboard(Key::E) {
+                //endless Eller maze from the current seed, scrolled with the wheel or the down arrow
+                scroll_rows(&mut window, &mut EllerRows::new(COLONES, None, StdRng::seed_from_u64(seed)), LINES as usize);
+                window.set_title(format!("THE MAZE - seed {}", seed));
+            }
+            else if button == Button::Keyboard(Key::D) {
+                movement = match movement {
+                    Movement::Four => Movement::Eight(Diagonals::default()),
+                    Movement::Eight(diagonals) if !diagonals.corner_cutting => Movement::Eight(Diagonals { corner_cutting: true, ..diagonals }),
+                    Movement::Eight(_) => Movement::Four,
+                };
+                labyrinth.set_movement(movement);
+                println!("Using {:?}", movement);
+            }
+            else if button == Button::Keyboard(Key::O) {
+                occupancy = !occupancy;
+                println!("Showing the maze as {}", if occupancy { "an occupancy grid" } else { "walls" });
+            }
+            else if button == Button::Keyboard(Key::T) {
+                paint_terrain(&mut labyrinth, &mut rng);
+                println!("Terrain painted");
             }
             else if button == Button::Keyboard(Key::F1) {
-                algo = Algo::BFS;
+                solver = Solver::Grid(Algo::BFS);
                 println!("Using BFS");
             } else if button == Button::Keyboard(Key::F2) {
-                algo = Algo::DFS;
+                solver = Solver::Grid(Algo::DFS);
                 println!("Using DFS");
             } else if button == Button::Keyboard(Key::F3) {
-                algo = Algo::Dijkstra;
+                solver = Solver::Grid(Algo::Dijkstra);
                 println!("Using Dijkstra");            
             } else if button == Button::Keyboard(Key::F4) {
-                algo = Algo::AStar;
+                solver = Solver::Grid(Algo::AStar);
                 println!("Using A Star");
+            } else if button == Button::Keyboard(Key::F5) {
+                //each press picks the next growing tree policy
+                generator = &GROWING_TREES[growing_tree];
+                println!("Using {} generator, {:?}", generator.name(), GROWING_TREES[growing_tree].selection);
+                growing_tree = (growing_tree + 1) % GROWING_TREES.len();
+                (labyrinth, rng) = generate(&mut window, seed, generator, braiding, movement);
+            } else if button == Button::Keyboard(Key::F6) {
+                solver = Solver::Grid(Algo::BidirectionalBFS);
+                println!("Using bidirectional BFS");
+            } else if button == Button::Keyboard(Key::F7) {
+                solver = Solver::Grid(Algo::BidirectionalDijkstra);
+                println!("Using bidirectional Dijkstra");
+            } else if button == Button::Keyboard(Key::F8) {
+                solver = Solver::Grid(Algo::IDDFS);
+                println!("Using iterative deepening DFS");
+            } else if button == Button::Keyboard(Key::F9) {
+                solver = Solver::Grid(Algo::IDAStar);
+                println!("Using IDA Star");
+            } else if button == Button::Keyboard(Key::F10) {
+                //jump point search always runs on the occupancy grid, 8-connected without corner cutting
+                solver = Solver::JumpPoints { precomputed: false };
+                println!("Using jump point search");
+            } else if button == Button::Keyboard(Key::F11) {
+                solver = Solver::JumpPoints { precomputed: true };
+                println!("Using JPS+");
+            } else if button == Button::Keyboard(Key::F12) {
+                //any-angle search, each press swaps between Theta* and its lazy variant
+                solver = match solver {
+                    Solver::Grid(Algo::ThetaStar) => Solver::Grid(Algo::LazyThetaStar),
+                    _ => Solver::Grid(Algo::ThetaStar),
+                };
+                println!("Using {}", if let Solver::Grid(Algo::ThetaStar) = solver { "Theta Star" } else { "Lazy Theta Star" });
+            } else if button == Button::Keyboard(Key::H) {
+                guidance.heuristic = match guidance.heuristic {
+                    None => Some(0),
+                    Some(i) if i + 1 < HEURISTICS.len() => Some(i + 1),
+                    Some(_) => None,
+                };
+                match guidance.heuristic {
+                    Some(i) => println!("Using {} heuristic", HEURISTICS[i].name()),
+                    None => println!("Using default heuristic"),
+                }
+            } else if button == Button::Keyboard(Key::W) {
+                guidance.weight = match guidance.weight {
+                    w if w < 1.5 => 1.5,
+                    w if w < 2.0 => 2.0,
+                    w if w < 5.0 => 5.0,
+                    _ => 1.0,
+                };
+                println!("Using heuristic weight {}", guidance.weight);
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,115 @@
+use crate::*;
+
+/// A grid where whole cells are either open or blocked, with no walls in between:
+/// any two open neighbours can be moved between. The usual format of game and benchmark maps.
+#[derive(Clone, Debug)]
+pub struct OccupancyGrid {
+    pub width: i32,
+    pub height: i32,
+    pub blocked: Vec<bool>,
+    pub movement: Movement,
+}
+
+impl OccupancyGrid {
+    /// Creates a grid where every cell is open.
+    pub fn new(width: i32, height: i32) -> OccupancyGrid {
+        assert!(width > 0 && height > 0, "grid must be at least 1x1");
+        OccupancyGrid { width, height, blocked: vec![false; (width * height) as usize], movement: Movement::Four }
+    }
+
+    /// Lays `maze` out on a grid twice as fine: node `(x, y)` becomes cell `(2x + 1, 2y + 1)`
+    /// and the cell between two nodes is open when they are linked. Terrain costs are not carried over.
+    pub fn from_maze(maze: &Maze) -> OccupancyGrid {
+        let mut grid = OccupancyGrid::new(2 * maze.width + 1, 2 * maze.height + 1);
+        grid.movement = maze.movement;
+        grid.blocked.iter_mut().for_each(|cell| *cell = true);
+        for node in maze.nodes.iter().filter(|node| !node.blocked) {
+            let (x, y) = (2 * node.x + 1, 2 * node.y + 1);
+            let index = grid.get_array_val(x, y);
+            grid.blocked[index] = false;
+            for d in node.neighbours.iter().filter(|d| !d.is_diagonal()) {
+                let (dx, dy) = d.offset();
+                let index = grid.get_array_val(x + dx, y + dy);
+                grid.blocked[index] = false;
+            }
+        }
+        grid
+    }
+
+    /// Whether `(x, y)` is inside the grid and not blocked.
+    pub fn is_open(&self, x: i32, y: i32) -> bool {
+        x >= 0 && x < self.width && y >= 0 && y < self.height && !self.blocked[self.get_array_val(x, y)]
+    }
+}
+
+impl Graph for OccupancyGrid {
+    fn len(&self) -> usize {
+        self.blocked.len()
+    }
+
+    fn edges(&self, node: NodeId) -> Vec<(NodeId, u32)> {
+        self.neighbours(node).into_iter().map(|(to, d)| (to, self.edge_cost(to, d))).collect()
+    }
+
+    fn position(&self, node: NodeId) -> Option<(i32, i32)> {
+        let (mut x, mut y) = (0, 0);
+        self.get_coord_vals(node, &mut x, &mut y);
+        Some((x, y))
+    }
+
+    fn incoming(&self, node: NodeId) -> Vec<(NodeId, u32)> {
+        self.neighbours(node).into_iter().map(|(from, d)| (from, self.edge_cost(node, d.opposite()))).collect()
+    }
+
+    fn movement(&self) -> Movement {
+        self.movement
+    }
+}
+
+impl Grid for OccupancyGrid {
+    fn width(&self) -> i32 {
+        self.width
+    }
+
+    fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn is_blocked(&self, index: usize) -> bool {
+        self.blocked[index]
+    }
+
+    fn neighbours(&self, index: usize) -> Vec<(usize, Direction)> {
+        //a blocked cell cannot be left or entered, whoever asks
+        if self.blocked[index] {
+            return Vec::new();
+        }
+        let (mut x, mut y) = (0, 0);
+        self.get_coord_vals(index, &mut x, &mut y);
+        let mut neighbours: Vec<(usize, Direction)> = Direction::ORTHOGONALS
+            .iter()
+            .filter(|d| self.is_open(x + d.offset().0, y + d.offset().1))
+            .map(|d| (self.get_array_val(x + d.offset().0, y + d.offset().1), *d))
+            .collect();
+
+        if let Movement::Eight(diagonals) = self.movement {
+            for d in Direction::DIAGONALS.iter() {
+                let (dx, dy) = d.offset();
+                if !self.is_open(x + dx, y + dy) {
+                    continue;
+                }
+                //same rule as the walled mazes, squeezing between two blocked corners is never allowed
+                let (beside_x, beside_y) = (self.is_open(x + dx, y), self.is_open(x, y + dy));
+                let open = if diagonals.corner_cutting { beside_x || beside_y } else { beside_x && beside_y };
+                if open {
+                    neighbours.push((self.get_array_val(x + dx, y + dy), *d));
+                }
+            }
+        }
+        neighbours
+    }
+
+    fn edge_cost(&self, _to: usize, d: Direction) -> u32 {
+        self.movement.step_cost(d)
+    }
+}
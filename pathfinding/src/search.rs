@@ -0,0 +1,107 @@
+use crate::bitset::BitSet;
+use crate::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Follows the antecedants back from `finish`, giving the path from `finish` to `start` as the solvers return it.
+pub(crate) fn trace_path(antecedants: &[usize], start: usize, finish: usize) -> Vec<usize> {
+    let mut path = Vec::new();
+    let mut index = finish;
+    while index != start {
+        path.push(index);
+        index = antecedants[index];
+    }
+    path.push(start);
+    path
+}
+
+/// What every best-first search keeps: distances and antecedants from the start,
+/// the open set ordered by score and the closed set of expanded nodes.
+pub(crate) struct BestFirst {
+    pub start: usize,
+    pub finish: usize,
+    pub distances: Vec<u32>,
+    pub antecedants: Vec<usize>,
+    //score each node was last queued with, entries left behind by a shorter path are skipped when popped
+    scores: Vec<u32>,
+    open: BinaryHeap<Reverse<(u32, usize)>>,
+    closed: BitSet,
+}
+
+impl BestFirst {
+    /// Search over `len` nodes with only `start` open, queued with `score`.
+    pub fn new(len: usize, start: usize, finish: usize, score: u32) -> BestFirst {
+        let mut search = BestFirst {
+            start,
+            finish,
+            distances: vec![u32::MAX; len],
+            antecedants: vec![usize::MAX; len],
+            scores: vec![u32::MAX; len],
+            open: BinaryHeap::new(),
+            closed: BitSet::new(len),
+        };
+        search.distances[start] = 0;
+        search.antecedants[start] = start;
+        search.scores[start] = score;
+        search.open.push(Reverse((score, start)));
+        search
+    }
+
+    pub fn reached(&self, index: usize) -> bool {
+        self.distances[index] != u32::MAX
+    }
+
+    pub fn is_closed(&self, index: usize) -> bool {
+        self.closed.contains(index)
+    }
+
+    pub fn close(&mut self, index: usize) {
+        self.closed.insert(index);
+    }
+
+    /// Score of the next open node, `None` once there is none.
+    pub fn peek(&mut self) -> Option<u32> {
+        while let Some(&Reverse((score, index))) = self.open.peek() {
+            if score == self.scores[index] && !self.closed.contains(index) {
+                return Some(score);
+            }
+            self.open.pop();
+        }
+        None
+    }
+
+    /// The open node with the lowest score. Once there is none, reports whether the finish was reached.
+    pub fn pop(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<usize> {
+        if self.peek().is_some() {
+            return self.open.pop().map(|Reverse((_, index))| index);
+        }
+        observer(if self.reached(self.finish) { SearchEvent::GoalReached } else { SearchEvent::Exhausted });
+        None
+    }
+
+    /// Takes `distance` through `from` as the way to `node` when it beats the known one,
+    /// reporting it and queuing `node` with `estimate` added. Returns whether it did.
+    pub fn relax(&mut self, node: usize, from: usize, distance: u32, estimate: u32, observer: &mut dyn FnMut(SearchEvent)) -> bool {
+        if distance >= self.distances[node] {
+            return false;
+        }
+        if !self.reached(node) {
+            observer(SearchEvent::NodeDiscovered { from, to: node });
+        }
+        self.distances[node] = distance;
+        self.antecedants[node] = from;
+        observer(SearchEvent::CostUpdated { node, from, cost: distance });
+        self.scores[node] = distance + estimate;
+        self.open.push(Reverse((distance + estimate, node)));
+        true
+    }
+
+    /// The path found from the finish back to the start, `None` when the finish was never reached.
+    pub fn path(&self) -> Option<Vec<usize>> {
+        if self.reached(self.finish) {
+            Some(trace_path(&self.antecedants, self.start, self.finish))
+        } else {
+            None
+        }
+    }
+}
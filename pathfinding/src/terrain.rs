@@ -0,0 +1,50 @@
+use crate::*;
+use rand::Rng;
+
+/// Named traversal costs a cell can be painted with.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Terrain {
+    Road,
+    Grass,
+    Mud,
+    Water,
+}
+
+impl Terrain {
+    pub const ALL: [Terrain; 4] = [Terrain::Road, Terrain::Grass, Terrain::Mud, Terrain::Water];
+
+    pub fn cost(self) -> u32 {
+        match self {
+            Terrain::Road => 1,
+            Terrain::Grass => 2,
+            Terrain::Mud => 4,
+            Terrain::Water => 8,
+        }
+    }
+
+    /// The most expensive terrain not costing more than `cost`.
+    pub fn from_cost(cost: u32) -> Terrain {
+        Terrain::ALL.iter().rev().copied().find(|t| t.cost() <= cost).unwrap_or(Terrain::Road)
+    }
+}
+
+/// Paints random round patches of grass, mud and water over the maze, leaving the rest as road.
+pub fn paint_terrain<R: Rng>(maze: &mut Maze, rng: &mut R) {
+    let patches = (maze.len() / 20).max(1);
+    let max_radius = (maze.width.min(maze.height) / 5).max(1);
+
+    for _ in 0..patches {
+        let terrain = Terrain::ALL[rng.gen_range(1..Terrain::ALL.len())];
+        let (cx, cy) = (rng.gen_range(0..maze.width), rng.gen_range(0..maze.height));
+        let radius = rng.gen_range(1..=max_radius);
+
+        for y in (cy - radius).max(0)..=(cy + radius).min(maze.height - 1) {
+            for x in (cx - radius).max(0)..=(cx + radius).min(maze.width - 1) {
+                if (x - cx) * (x - cx) + (y - cy) * (y - cy) <= radius * radius {
+                    let index = maze.get_array_val(x, y);
+                    maze.nodes[index].cost = terrain.cost();
+                }
+            }
+        }
+    }
+}
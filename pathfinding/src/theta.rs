@@ -0,0 +1,174 @@
+use crate::search::BestFirst;
+use crate::*;
+
+/// Whether one straight step leads from `from` to `to`, through an open wall or between two open cells.
+fn passable(grid: &dyn Grid, from: usize, to: usize) -> bool {
+    grid.neighbours(from).iter().any(|(index, d)| *index == to && !d.is_diagonal())
+}
+
+/// Whether the segment between the centres of cells `from` and `to` stays clear of walls and blocked cells.
+/// A segment going exactly through a corner passes as a diagonal step of the grid would: one way around it open
+/// is enough when the grid cuts corners, both are needed otherwise.
+pub fn line_of_sight(grid: &dyn Grid, from: usize, to: usize) -> bool {
+    let (mut x, mut y, mut tx, mut ty) = (0, 0, 0, 0);
+    grid.get_coord_vals(from, &mut x, &mut y);
+    grid.get_coord_vals(to, &mut tx, &mut ty);
+    let (dx, dy) = ((tx - x).abs(), (ty - y).abs());
+    let (sx, sy) = ((tx - x).signum(), (ty - y).signum());
+    let corner_cutting = match grid.movement() {
+        Movement::Eight(diagonals) => diagonals.corner_cutting,
+        Movement::Four => false,
+    };
+
+    //walks every cell the segment crosses, error tells which cell border comes next
+    let mut error = dx - dy;
+    let mut index = from;
+    while (x, y) != (tx, ty) {
+        if error > 0 {
+            x += sx;
+            error -= 2 * dy;
+        } else if error < 0 {
+            y += sy;
+            error += 2 * dx;
+        } else {
+            let beside = grid.get_array_val(x + sx, y);
+            let below = grid.get_array_val(x, y + sy);
+            let next = grid.get_array_val(x + sx, y + sy);
+            let around_beside = passable(grid, index, beside) && passable(grid, beside, next);
+            let around_below = passable(grid, index, below) && passable(grid, below, next);
+            let around = if corner_cutting { around_beside || around_below } else { around_beside && around_below };
+            if !around {
+                return false;
+            }
+            x += sx;
+            y += sy;
+            error += 2 * (dx - dy);
+            index = next;
+            continue;
+        }
+        let next = grid.get_array_val(x, y);
+        if !passable(grid, index, next) {
+            return false;
+        }
+        index = next;
+    }
+    true
+}
+
+/// Length of the segment between the centres of cells `a` and `b`, in hundredths of a cell.
+fn length(grid: &dyn Grid, a: usize, b: usize) -> u32 {
+    let (mut x, mut y, mut bx, mut by) = (0, 0, 0, 0);
+    grid.get_coord_vals(a, &mut x, &mut y);
+    grid.get_coord_vals(b, &mut bx, &mut by);
+    (f64::from((x - bx) * (x - bx) + (y - by) * (y - by)).sqrt() * 100.0).round() as u32
+}
+
+/// A* where a node may take the antecedant of its antecedant when it can see it, so paths cut across open space at any angle.
+/// Lazy Theta* assumes the line of sight when a node is discovered and only checks it once the node is expanded.
+struct AnyAngle<'a> {
+    grid: &'a dyn Grid,
+    lazy: bool,
+    search: BestFirst,
+}
+
+impl<'a> AnyAngle<'a> {
+    fn new(grid: &'a dyn Grid, start: usize, finish: usize, lazy: bool) -> AnyAngle<'a> {
+        AnyAngle { grid, lazy, search: BestFirst::new(grid.len(), start, finish, length(grid, start, finish)) }
+    }
+
+    //the straight line to the finish, never longer than a path around walls
+    fn estimate(&self, index: usize) -> u32 {
+        length(self.grid, index, self.search.finish)
+    }
+
+    /// Lazy Theta* only: when `index` cannot see the antecedant it was given, falls back on the best expanded neighbour.
+    fn set_vertex(&mut self, index: usize, observer: &mut dyn FnMut(SearchEvent)) {
+        if !self.lazy || line_of_sight(self.grid, self.search.antecedants[index], index) {
+            return;
+        }
+        let (distance, antecedant) = self
+            .grid
+            .neighbours(index)
+            .into_iter()
+            .filter(|(neighbour, _)| self.search.is_closed(*neighbour))
+            .map(|(neighbour, _)| (self.search.distances[neighbour] + length(self.grid, neighbour, index), neighbour))
+            .min()
+            .expect("a discovered node has an expanded neighbour");
+        self.search.distances[index] = distance;
+        self.search.antecedants[index] = antecedant;
+        observer(SearchEvent::CostUpdated { node: index, from: antecedant, cost: distance });
+    }
+
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        while self.iter_solve(&mut |_| {}).is_none() {}
+        self.search.path()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        let node_index = match self.search.pop(observer) {
+            Some(index) => index,
+            None => return Some(false),
+        };
+
+        self.set_vertex(node_index, observer);
+        if node_index == self.search.finish {
+            observer(SearchEvent::GoalReached);
+            return Some(true);
+        }
+        observer(SearchEvent::NodeExpanded(node_index));
+
+        let parent = self.search.antecedants[node_index];
+        for (neighbour_index, _) in self.grid.neighbours(node_index) {
+            if self.search.is_closed(neighbour_index) {
+                continue;
+            }
+            //straight from the antecedant when it is in sight, through this node otherwise
+            let from = if self.lazy || line_of_sight(self.grid, parent, neighbour_index) { parent } else { node_index };
+            let distance = self.search.distances[from] + length(self.grid, from, neighbour_index);
+            let estimate = self.estimate(neighbour_index);
+            self.search.relax(neighbour_index, from, distance, estimate, observer);
+        }
+
+        self.search.close(node_index);
+        None
+    }
+}
+
+/// Theta*: any-angle paths over a grid, given as the cells where they turn, each in sight of the next.
+/// Costs are segment lengths in hundredths of a cell, terrain and movement costs play no part.
+pub struct ThetaStar<'a>(AnyAngle<'a>);
+
+impl<'a> ThetaStar<'a> {
+    pub fn new(grid: &'a dyn Grid, start: usize, finish: usize) -> ThetaStar<'a> {
+        ThetaStar(AnyAngle::new(grid, start, finish, false))
+    }
+}
+
+impl SearchAlgo for ThetaStar<'_> {
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        self.0.solve()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.0.iter_solve(observer)
+    }
+}
+
+/// Lazy Theta*: the paths of `ThetaStar` for one line of sight check per expanded node instead of one per neighbour.
+pub struct LazyThetaStar<'a>(AnyAngle<'a>);
+
+impl<'a> LazyThetaStar<'a> {
+    pub fn new(grid: &'a dyn Grid, start: usize, finish: usize) -> LazyThetaStar<'a> {
+        LazyThetaStar(AnyAngle::new(grid, start, finish, true))
+    }
+}
+
+impl SearchAlgo for LazyThetaStar<'_> {
+    fn solve(&mut self) -> Option<Vec<usize>> {
+        self.0.solve()
+    }
+
+    fn iter_solve(&mut self, observer: &mut dyn FnMut(SearchEvent)) -> Option<bool> {
+        self.0.iter_solve(observer)
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::path_cost;
+use pathfinding::adjacency::AdjacencyList;
+use pathfinding::astar::AStar;
+use pathfinding::deepening::IDAStar;
+use pathfinding::dijkstra::Dijkstra;
+use pathfinding::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Road network: edges cost their length, rounded up, stretched by up to half as much again.
+fn road_network(nodes: usize, roads: usize, rng: &mut StdRng) -> AdjacencyList {
+    let mut graph = AdjacencyList::new();
+    for _ in 0..nodes {
+        graph.add_node(Some((rng.gen_range(0..100), rng.gen_range(0..100))));
+    }
+    for _ in 0..roads {
+        let (a, b) = (rng.gen_range(0..nodes), rng.gen_range(0..nodes));
+        let (ax, ay) = graph.positions[a].unwrap();
+        let (bx, by) = graph.positions[b].unwrap();
+        let length = f64::from(ax - bx).hypot(f64::from(ay - by));
+        let cost = (length * rng.gen_range(1.0..1.5)).ceil().max(1.0) as u32;
+        graph.connect(a, b, cost);
+    }
+    graph
+}
+
+fn check(graph: &AdjacencyList, start: usize, finish: usize, with_ida: bool) {
+    let expected = Dijkstra::new(graph, start, finish).solve().map(|path| path_cost(graph, &path));
+    let astar = AStar::new(graph, start, finish).solve().map(|path| path_cost(graph, &path));
+    assert_eq!(astar, expected, "A* from {} to {}", start, finish);
+    if with_ida {
+        let ida = IDAStar::new(graph, start, finish).solve().map(|path| path_cost(graph, &path));
+        assert_eq!(ida, expected, "IDA* from {} to {}", start, finish);
+    }
+}
+
+#[test]
+fn astar_takes_the_longer_looking_cheaper_road() {
+    let mut graph = AdjacencyList::new();
+    let s = graph.add_node(Some((0, 0)));
+    let x = graph.add_node(Some((10, 10)));
+    let y = graph.add_node(Some((10, 0)));
+    let g = graph.add_node(Some((20, 0)));
+    graph.connect(s, x, 15);
+    graph.connect(x, g, 15);
+    graph.connect(s, y, 10);
+    graph.connect(y, g, 24);
+
+    assert_eq!(AStar::new(&graph, s, g).solve(), Some(vec![g, x, s]));
+    assert_eq!(IDAStar::new(&graph, s, g).solve(), Some(vec![g, x, s]));
+    check(&graph, s, g, true);
+}
+
+#[test]
+fn astar_matches_dijkstra_on_road_networks() {
+    let mut rng = StdRng::seed_from_u64(21);
+    for _ in 0..50 {
+        let graph = road_network(30, 45, &mut rng);
+        for _ in 0..10 {
+            check(&graph, rng.gen_range(0..30), rng.gen_range(0..30), false);
+        }
+    }
+    //iterative deepening only on small networks, it goes over every simple path
+    for _ in 0..50 {
+        let graph = road_network(8, 10, &mut rng);
+        for _ in 0..5 {
+            check(&graph, rng.gen_range(0..8), rng.gen_range(0..8), true);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::{path_cost, random_grid};
+use pathfinding::adjacency::AdjacencyList;
+use pathfinding::bfs::BFS;
+use pathfinding::bidirectional::{BidirectionalBFS, BidirectionalDijkstra};
+use pathfinding::dijkstra::Dijkstra;
+use pathfinding::generators::GENERATORS;
+use pathfinding::occupancy::OccupancyGrid;
+use pathfinding::terrain::paint_terrain;
+use pathfinding::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Both bidirectional searches against their one-way counterparts between `start` and `finish`.
+fn check(graph: &dyn Graph, start: usize, finish: usize) {
+    let dijkstra = Dijkstra::new(graph, start, finish).solve();
+    let bidirectional = BidirectionalDijkstra::new(graph, start, finish).solve();
+    for path in dijkstra.iter().chain(bidirectional.iter()) {
+        assert_eq!((path.first(), path.last()), (Some(&finish), Some(&start)));
+    }
+    assert_eq!(
+        bidirectional.as_ref().map(|path| path_cost(graph, path)),
+        dijkstra.as_ref().map(|path| path_cost(graph, path)),
+        "bidirectional Dijkstra from {} to {}",
+        start,
+        finish
+    );
+
+    let bfs = BFS::new(graph, start, finish).solve();
+    let bidirectional = BidirectionalBFS::new(graph, start, finish).solve();
+    if let Some(path) = bidirectional.as_ref() {
+        assert_eq!((path.first(), path.last()), (Some(&finish), Some(&start)));
+        path_cost(graph, path);
+    }
+    assert_eq!(bidirectional.map(|path| path.len()), bfs.map(|path| path.len()), "bidirectional BFS from {} to {}", start, finish);
+}
+
+fn check_pairs(graph: &dyn Graph, pairs: usize, rng: &mut StdRng) {
+    for _ in 0..pairs {
+        check(graph, rng.gen_range(0..graph.len()), rng.gen_range(0..graph.len()));
+    }
+}
+
+#[test]
+fn bidirectional_matches_one_way_searches_on_mazes() {
+    let mut rng = StdRng::seed_from_u64(22);
+    for seed in 0..40 {
+        let mut maze = create_labyrinth(12, 9, GENERATORS[seed % GENERATORS.len()], &mut rng);
+        if seed % 3 != 0 {
+            //weighted cells make the stopping rule matter
+            paint_terrain(&mut maze, &mut rng);
+        }
+        if seed % 2 == 1 {
+            maze.set_movement(Movement::Eight(Diagonals::default()));
+        }
+        check_pairs(&maze, 20, &mut rng);
+    }
+}
+
+#[test]
+fn bidirectional_matches_one_way_searches_on_occupancy_grids() {
+    let mut rng = StdRng::seed_from_u64(22);
+    for round in 0..40 {
+        let movement = if round % 2 == 1 {
+            Movement::Eight(Diagonals { straight_cost: 5, diagonal_cost: 7, corner_cutting: round % 4 == 1 })
+        } else {
+            Movement::Four
+        };
+        let (width, height) = (rng.gen_range(1..25), rng.gen_range(1..25));
+        let grid = random_grid(width, height, 0.3, movement, &mut rng);
+        //blocked endpoints included, they are never reached
+        check_pairs(&grid, 20, &mut rng);
+    }
+}
+
+#[test]
+fn bidirectional_matches_one_way_searches_on_one_way_edges() {
+    let mut rng = StdRng::seed_from_u64(22);
+    for _ in 0..40 {
+        let mut graph = AdjacencyList::new();
+        for _ in 0..20 {
+            graph.add_node(None);
+        }
+        for _ in 0..35 {
+            graph.add_edge(rng.gen_range(0..20), rng.gen_range(0..20), rng.gen_range(1..10));
+        }
+        check_pairs(&graph, 20, &mut rng);
+    }
+}
+
+#[test]
+fn bidirectional_reports_blocked_and_unreachable_finishes() {
+    let mut grid = OccupancyGrid::new(3, 1);
+    grid.blocked[2] = true;
+    assert_eq!(BidirectionalDijkstra::new(&grid, 0, 2).solve(), None);
+    assert_eq!(BidirectionalBFS::new(&grid, 0, 2).solve(), None);
+    assert_eq!(BidirectionalDijkstra::new(&grid, 2, 0).solve(), None);
+    assert_eq!(BidirectionalBFS::new(&grid, 2, 0).solve(), None);
+    check(&grid, 0, 2);
+    check(&grid, 2, 0);
+
+    //two rooms with nothing between them
+    let mut graph = AdjacencyList::new();
+    let nodes: Vec<NodeId> = (0..4).map(|_| graph.add_node(None)).collect();
+    graph.connect(nodes[0], nodes[1], 3);
+    graph.connect(nodes[2], nodes[3], 3);
+    assert_eq!(BidirectionalDijkstra::new(&graph, nodes[0], nodes[3]).solve(), None);
+    assert_eq!(BidirectionalBFS::new(&graph, nodes[0], nodes[3]).solve(), None);
+    check(&graph, nodes[1], nodes[2]);
+}
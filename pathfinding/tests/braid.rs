@@ -0,0 +1,27 @@
+mod common;
+
+use common::perfect_generators;
+use pathfinding::generators::braid::braid;
+use pathfinding::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn dead_ends(maze: &Maze) -> usize {
+    (0..maze.len()).filter(|i| maze[*i].neighbours.iter().filter(|d| !d.is_diagonal()).count() == 1).count()
+}
+
+#[test]
+fn braid_removes_the_requested_fraction_of_dead_ends() {
+    //perfect mazes only, every dead end there has a wall to knock out
+    let generators = perfect_generators();
+    for seed in 0..100 {
+        for fraction in [0.1, 0.25, 0.5, 0.75, 1.0].iter() {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut maze = create_labyrinth(20, 15, generators[seed as usize % generators.len()], &mut rng);
+            let before = dead_ends(&maze);
+            braid(&mut maze, *fraction, &mut rng, &mut |_| {});
+            let kept = before - (before as f64 * fraction).round() as usize;
+            assert_eq!(dead_ends(&maze), kept, "seed {} removing {} of {} dead ends", seed, fraction, before);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//helpers shared by the integration tests, each of them uses its own subset
+#![allow(dead_code)]
+
+use pathfinding::generators::growing_tree::GROWING_TREES;
+use pathfinding::generators::{MazeGenerator, GENERATORS};
+use pathfinding::occupancy::OccupancyGrid;
+use pathfinding::*;
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// Cost of a path as the solvers return it, from the finish back to the start, checking every step is an edge.
+pub fn path_cost(graph: &dyn Graph, path: &[usize]) -> u32 {
+    path.windows(2)
+        .map(|w| {
+            let step = graph.edges(w[1]).into_iter().filter(|(to, _)| *to == w[0]).map(|(_, cost)| cost).min();
+            step.unwrap_or_else(|| panic!("no edge from {} to {}", w[1], w[0]))
+        })
+        .sum()
+}
+
+/// Grid with each cell blocked with probability `fill`.
+pub fn random_grid(width: i32, height: i32, fill: f64, movement: Movement, rng: &mut StdRng) -> OccupancyGrid {
+    let mut grid = OccupancyGrid::new(width, height);
+    grid.movement = movement;
+    grid.blocked.iter_mut().for_each(|cell| *cell = rng.gen_bool(fill));
+    grid
+}
+
+/// The generators whose mazes have exactly one path between any two cells.
+pub fn perfect_generators() -> Vec<&'static dyn MazeGenerator> {
+    //random walls, caves and dungeons are loopy by design
+    GENERATORS[1..8].iter().copied().chain(GROWING_TREES.iter().map(|g| g as &dyn MazeGenerator)).collect()
+}
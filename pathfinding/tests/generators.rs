@@ -0,0 +1,48 @@
+mod common;
+
+use common::perfect_generators;
+use pathfinding::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Every open cell reachable from every other by exactly one path: the links form a spanning tree of the open cells.
+fn check_perfect(maze: &Maze, name: &str) {
+    let open: Vec<usize> = (0..maze.len()).filter(|i| !maze[*i].blocked).collect();
+    let mut links = 0;
+    for &index in open.iter() {
+        for d in maze[index].neighbours.iter() {
+            let neighbour = maze.neighbour(index, *d).expect("links stay inside the maze");
+            assert!(!maze[neighbour].blocked, "{}: {} links into a blocked cell", name, index);
+            assert!(maze[neighbour].neighbours.contains(&d.opposite()), "{}: link from {} is one-way", name, index);
+            links += 1;
+        }
+    }
+
+    //a connected graph with one edge less than it has nodes has no loop
+    let mut reached = vec![false; maze.len()];
+    let mut stack: Vec<usize> = open.first().copied().into_iter().collect();
+    let mut count = 0;
+    while let Some(index) = stack.pop() {
+        if reached[index] {
+            continue;
+        }
+        reached[index] = true;
+        count += 1;
+        stack.extend(maze[index].neighbours.iter().filter_map(|d| maze.neighbour(index, *d)));
+    }
+    assert_eq!(count, open.len(), "{}: {}x{} has unreachable cells", name, maze.width, maze.height);
+    assert_eq!(links / 2 + 1, open.len(), "{}: {}x{} has loops", name, maze.width, maze.height);
+}
+
+#[test]
+fn generators_make_perfect_mazes() {
+    for (i, generator) in perfect_generators().iter().enumerate() {
+        for width in 1..=30 {
+            for height in (1..=20).step_by(3) {
+                let mut rng = StdRng::seed_from_u64((i * 1000 + width * 20 + height) as u64);
+                let maze = create_labyrinth(width as i32, height as i32, *generator, &mut rng);
+                check_perfect(&maze, generator.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use common::{path_cost, random_grid};
+use pathfinding::astar::AStar;
+use pathfinding::generators::GENERATORS;
+use pathfinding::jps::{JumpTable, JPSPlus, JPS};
+use pathfinding::occupancy::OccupancyGrid;
+use pathfinding::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn maze_grid(seed: u64) -> OccupancyGrid {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let generator = GENERATORS[seed as usize % GENERATORS.len()];
+    let mut maze = create_labyrinth(12, 9, generator, &mut rng);
+    maze.set_movement(Movement::Eight(Diagonals::default()));
+    OccupancyGrid::from_maze(&maze)
+}
+
+/// Cost of a path given as jump points, walking every straight or diagonal run between them.
+fn jump_path_cost(grid: &OccupancyGrid, path: &[usize]) -> u32 {
+    let mut cost = 0;
+    for w in path.windows(2) {
+        let (mut x, mut y, mut tx, mut ty) = (0, 0, 0, 0);
+        grid.get_coord_vals(w[1], &mut x, &mut y);
+        grid.get_coord_vals(w[0], &mut tx, &mut ty);
+        let (dx, dy) = ((tx - x).signum(), (ty - y).signum());
+        assert!(dx == 0 || dy == 0 || (tx - x).abs() == (ty - y).abs(), "jump off a line from ({}, {}) to ({}, {})", x, y, tx, ty);
+        while (x, y) != (tx, ty) {
+            let from = grid.get_array_val(x, y);
+            let to = grid.get_array_val(x + dx, y + dy);
+            let step = grid.edges(from).iter().find(|(next, _)| *next == to).map(|(_, cost)| *cost);
+            cost += step.unwrap_or_else(|| panic!("no step from ({}, {}) to ({}, {})", x, y, x + dx, y + dy));
+            x += dx;
+            y += dy;
+        }
+    }
+    cost
+}
+
+/// Solves a few pairs of open cells with A*, JPS and JPS+ and compares the costs.
+fn check_grid(grid: &OccupancyGrid, rng: &mut StdRng) {
+    check_against(grid, grid, rng);
+}
+
+/// Same as `check_grid`, with A* running on `reference`, the same cells with the movement JPS uses on `grid`.
+fn check_against(grid: &OccupancyGrid, reference: &OccupancyGrid, rng: &mut StdRng) {
+    let table = JumpTable::new(grid);
+    let open: Vec<usize> = (0..grid.len()).filter(|i| !grid.is_blocked(*i)).collect();
+    if open.is_empty() {
+        return;
+    }
+    for _ in 0..20 {
+        let start = open[rng.gen_range(0..open.len())];
+        let finish = open[rng.gen_range(0..open.len())];
+        let expected = AStar::new(reference, start, finish).solve().map(|path| path_cost(reference, &path));
+        let jps = JPS::new(grid, start, finish).solve();
+        let jps_plus = JPSPlus::new(grid, &table, start, finish).solve();
+        for path in [jps, jps_plus].iter() {
+            if let Some(path) = path {
+                assert_eq!(path.first(), Some(&finish));
+                assert_eq!(path.last(), Some(&start));
+            }
+            assert_eq!(path.as_ref().map(|path| jump_path_cost(reference, path)), expected, "from {} to {}", start, finish);
+        }
+    }
+}
+
+#[test]
+fn jps_matches_astar_on_random_grids() {
+    let mut rng = StdRng::seed_from_u64(24);
+    for fill in [0.0, 0.1, 0.25, 0.4].iter() {
+        for _ in 0..10 {
+            let grid = random_grid(rng.gen_range(1..30), rng.gen_range(1..30), *fill, Movement::Eight(Diagonals::default()), &mut rng);
+            check_grid(&grid, &mut rng);
+        }
+    }
+}
+
+#[test]
+fn jps_matches_astar_on_mazes() {
+    let mut rng = StdRng::seed_from_u64(24);
+    for seed in 0..20 {
+        check_grid(&maze_grid(seed), &mut rng);
+    }
+}
+
+#[test]
+fn jps_matches_astar_with_weighted_diagonals() {
+    let mut rng = StdRng::seed_from_u64(24);
+    for _ in 0..10 {
+        let mut grid = random_grid(20, 20, 0.2, Movement::Eight(Diagonals::default()), &mut rng);
+        grid.movement = Movement::Eight(Diagonals { straight_cost: 5, diagonal_cost: 7, corner_cutting: false });
+        check_grid(&grid, &mut rng);
+    }
+}
+
+#[test]
+fn jps_falls_back_on_default_costs_outside_its_range() {
+    let mut rng = StdRng::seed_from_u64(24);
+    let costs = [(10, 25), (10, 8), (3, 7), (5, 4)];
+    for round in 0..20 {
+        let mut grid = random_grid(20, 15, 0.25, Movement::Eight(Diagonals::default()), &mut rng);
+        let reference = grid.clone();
+        let (straight_cost, diagonal_cost) = costs[round % costs.len()];
+        grid.movement = Movement::Eight(Diagonals { straight_cost, diagonal_cost, corner_cutting: false });
+        check_against(&grid, &reference, &mut rng);
+    }
+}
+
+#[test]
+fn jps_reports_unreachable_finish() {
+    //a full wall down the middle
+    let mut grid = OccupancyGrid::new(5, 5);
+    grid.movement = Movement::Eight(Diagonals::default());
+    for y in 0..5 {
+        let index = grid.get_array_val(2, y);
+        grid.blocked[index] = true;
+    }
+    let table = JumpTable::new(&grid);
+    let (start, finish) = (grid.get_array_val(0, 0), grid.get_array_val(4, 4));
+    assert_eq!(JPS::new(&grid, start, finish).solve(), None);
+    assert_eq!(JPSPlus::new(&grid, &table, start, finish).solve(), None);
+}
+
+#[test]
+fn jps_moves_without_corner_cutting_on_any_grid() {
+    let mut rng = StdRng::seed_from_u64(24);
+    for round in 0..20 {
+        let mut grid = random_grid(20, 15, 0.25, Movement::Eight(Diagonals::default()), &mut rng);
+        let reference = grid.clone();
+        grid.movement = if round % 2 == 0 {
+            Movement::Four
+        } else {
+            Movement::Eight(Diagonals { corner_cutting: true, ..Diagonals::default() })
+        };
+        check_against(&grid, &reference, &mut rng);
+    }
+}
+
+#[test]
+fn jps_never_leaves_or_enters_blocked_cells() {
+    let mut grid = OccupancyGrid::new(3, 3);
+    grid.movement = Movement::Eight(Diagonals::default());
+    grid.blocked[4] = true;
+    let table = JumpTable::new(&grid);
+    for &(start, finish) in [(4, 0), (0, 4), (4, 8)].iter() {
+        assert_eq!(AStar::new(&grid, start, finish).solve(), None);
+        assert_eq!(JPS::new(&grid, start, finish).solve(), None);
+        assert_eq!(JPSPlus::new(&grid, &table, start, finish).solve(), None);
+    }
+}
@@ -0,0 +1,25 @@
+use pathfinding::astar::AStar;
+use pathfinding::bfs::BFS;
+use pathfinding::dijkstra::Dijkstra;
+use pathfinding::occupancy::OccupancyGrid;
+use pathfinding::*;
+
+#[test]
+fn blocked_cells_have_no_neighbours_either_way() {
+    let movements = [Movement::Four, Movement::Eight(Diagonals::default()), Movement::Eight(Diagonals { corner_cutting: true, ..Diagonals::default() })];
+    for movement in movements.iter() {
+        //the centre of a 3x3 grid is blocked, every other cell is open
+        let mut grid = OccupancyGrid::new(3, 3);
+        grid.movement = *movement;
+        grid.blocked[4] = true;
+        assert!(grid.neighbours(4).is_empty());
+        for index in (0..9).filter(|index| *index != 4) {
+            assert!(grid.neighbours(index).iter().all(|(to, _)| *to != 4), "{} leads into the blocked centre", index);
+        }
+        for &(start, finish) in [(4, 0), (0, 4), (4, 8)].iter() {
+            assert_eq!(BFS::new(&grid, start, finish).solve(), None);
+            assert_eq!(Dijkstra::new(&grid, start, finish).solve(), None);
+            assert_eq!(AStar::new(&grid, start, finish).solve(), None);
+        }
+    }
+}
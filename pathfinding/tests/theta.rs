@@ -0,0 +1,89 @@
+mod common;
+
+use common::random_grid;
+use pathfinding::generators::GENERATORS;
+use pathfinding::occupancy::OccupancyGrid;
+use pathfinding::theta::{line_of_sight, LazyThetaStar, ThetaStar};
+use pathfinding::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn diagonals(corner_cutting: bool) -> Movement {
+    Movement::Eight(Diagonals { corner_cutting, ..Diagonals::default() })
+}
+
+/// Every diagonal step the grid offers is in sight, and every diagonal pair in sight is a step of the grid.
+fn check_diagonal_steps(grid: &dyn Grid) {
+    for index in 0..grid.len() {
+        let (mut x, mut y) = (0, 0);
+        grid.get_coord_vals(index, &mut x, &mut y);
+        let steps = grid.neighbours(index);
+        for &(dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)].iter() {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < 0 || ny < 0 || nx >= grid.width() || ny >= grid.height() {
+                continue;
+            }
+            let next = grid.get_array_val(nx, ny);
+            let is_step = steps.iter().any(|(to, _)| *to == next);
+            assert_eq!(line_of_sight(grid, index, next), is_step, "from ({}, {}) to ({}, {})", x, y, nx, ny);
+        }
+    }
+}
+
+/// Both searches between a few pairs of open cells, every segment of their paths in sight.
+fn check_paths(grid: &dyn Grid, rng: &mut StdRng) {
+    let open: Vec<usize> = (0..grid.len()).filter(|i| !grid.is_blocked(*i)).collect();
+    for _ in 0..20 {
+        let start = open[rng.gen_range(0..open.len())];
+        let finish = open[rng.gen_range(0..open.len())];
+        let paths = [ThetaStar::new(grid, start, finish).solve(), LazyThetaStar::new(grid, start, finish).solve()];
+        for path in paths.iter().flatten() {
+            assert_eq!((path.first(), path.last()), (Some(&finish), Some(&start)));
+            for w in path.windows(2) {
+                assert!(line_of_sight(grid, w[1], w[0]), "segment from {} to {} out of sight", w[1], w[0]);
+            }
+        }
+    }
+}
+
+#[test]
+fn line_of_sight_follows_corner_cutting() {
+    //only the way around through the top right cell is open
+    let mut grid = OccupancyGrid::new(2, 2);
+    grid.blocked[2] = true;
+    grid.movement = diagonals(true);
+    assert!(line_of_sight(&grid, 0, 3));
+    assert_eq!(ThetaStar::new(&grid, 0, 3).solve(), Some(vec![3, 0]));
+    assert_eq!(LazyThetaStar::new(&grid, 0, 3).solve(), Some(vec![3, 0]));
+
+    grid.movement = diagonals(false);
+    assert!(!line_of_sight(&grid, 0, 3));
+    assert_eq!(ThetaStar::new(&grid, 0, 3).solve(), Some(vec![3, 1, 0]));
+}
+
+#[test]
+fn line_of_sight_agrees_with_diagonal_steps() {
+    let mut rng = StdRng::seed_from_u64(25);
+    for round in 0..20 {
+        let grid = random_grid(15, 12, 0.3, diagonals(round % 2 == 0), &mut rng);
+        check_diagonal_steps(&grid);
+
+        let mut maze = create_labyrinth(9, 7, GENERATORS[round % GENERATORS.len()], &mut rng);
+        maze.set_movement(diagonals(round % 2 == 0));
+        check_diagonal_steps(&maze);
+    }
+}
+
+#[test]
+fn theta_paths_stay_in_sight_when_cutting_corners() {
+    let mut rng = StdRng::seed_from_u64(25);
+    for round in 0..20 {
+        let mut grid = random_grid(20, 15, 0.25, diagonals(true), &mut rng);
+        grid.blocked[0] = false;
+        check_paths(&grid, &mut rng);
+
+        let mut maze = create_labyrinth(9, 7, GENERATORS[round % GENERATORS.len()], &mut rng);
+        maze.set_movement(diagonals(true));
+        check_paths(&maze, &mut rng);
+    }
+}